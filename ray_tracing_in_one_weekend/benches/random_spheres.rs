@@ -0,0 +1,141 @@
+//! Compares the closest intersection search over ~10k random spheres
+//! by testing every sphere against the bounding volume hierarchy traversal.
+//!
+//! Run with `cargo bench --bench random_spheres`
+
+use std::{num::NonZeroUsize, ops::Range, sync::Arc, time::Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use ray_tracing_in_one_weekend::{
+    scene::{
+        bvh::Bvh,
+        camera::Camera,
+        materials::{dielectric::Dielectric, lambertian::Lambertian, metal::Metal, Material},
+        scene_objects::{sphere::Sphere, HitRecord, SceneObject},
+        Scene,
+    },
+    vector::{Ray, Vector},
+};
+
+const GRID_SIZE: i32 = 100;
+const RAYS_NUM: usize = 20_000;
+const T_RANGE: Range<f64> = 0.0001..f64::INFINITY;
+
+/// Ground sphere with a `GRID_SIZE` x `GRID_SIZE` grid of small randomly placed spheres
+fn gen_random_spheres(rng: &mut StdRng) -> Vec<Box<dyn SceneObject>> {
+    let mut spheres: Vec<Box<dyn SceneObject>> = vec![Box::new(Sphere::new(
+        Vector::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+    ))];
+
+    for i in -GRID_SIZE / 2..GRID_SIZE / 2 {
+        for j in -GRID_SIZE / 2..GRID_SIZE / 2 {
+            let center = Vector::new(
+                i as f64 + 0.9 * rng.gen_range(0.0..1.0),
+                0.2,
+                j as f64 + 0.9 * rng.gen_range(0.0..1.0),
+            );
+
+            let color = Vector::new(
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(0.0..1.0),
+            );
+            let material: Arc<dyn Material> = match rng.gen_range(0.0..1.0) {
+                p if p < 0.8 => Arc::new(Lambertian::new(color)),
+                p if p < 0.95 => Arc::new(Metal::new(color, Some(rng.gen_range(0.0..0.5)))),
+                _ => Arc::new(Dielectric::new(1.5)),
+            };
+
+            spheres.push(Box::new(Sphere::new(center, 0.2, material)));
+        }
+    }
+
+    spheres
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let spheres = gen_random_spheres(&mut rng);
+
+    let look_from = Vector::new(13.0, 2.0, 3.0);
+    let rays = (0..RAYS_NUM)
+        .map(|_| {
+            let target = Vector::new(
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(0.0..2.0),
+                rng.gen_range(-10.0..10.0),
+            );
+
+            Ray::new(look_from, target - look_from)
+        })
+        .collect::<Vec<_>>();
+
+    let linear_closest = |ray: &Ray| -> Option<HitRecord> {
+        spheres
+            .iter()
+            .fold(None, |closest: Option<HitRecord>, sphere| {
+                let t_max = closest.map_or(T_RANGE.end, |hit| hit.t);
+
+                sphere
+                    .calc_ray_intersection(ray, T_RANGE.start..t_max)
+                    .or(closest)
+            })
+    };
+
+    let start = Instant::now();
+    let linear_hits = rays.iter().map(linear_closest).collect::<Vec<_>>();
+    let linear_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let bounding_boxes = spheres
+        .iter()
+        .map(|sphere| sphere.get_bounding_box())
+        .collect::<Vec<_>>();
+    let bvh = Bvh::new(&bounding_boxes);
+    let build_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let bvh_hits = rays
+        .iter()
+        .map(|ray| {
+            bvh.calc_closest_intersection(ray, T_RANGE, |i, t_range| {
+                spheres[i].calc_ray_intersection(ray, t_range)
+            })
+            .map(|(_, hit)| hit)
+        })
+        .collect::<Vec<_>>();
+    let bvh_elapsed = start.elapsed();
+
+    let mismatches = linear_hits
+        .iter()
+        .zip(bvh_hits.iter())
+        .filter(|(a, b)| a.map(|hit| hit.t) != b.map(|hit| hit.t))
+        .count();
+    assert_eq!(mismatches, 0, "BVH closest hits differ from linear search");
+
+    println!("spheres: {}, rays: {RAYS_NUM}", spheres.len());
+    println!("linear search: {linear_elapsed:?}");
+    println!("bvh build:     {build_elapsed:?}");
+    println!("bvh search:    {bvh_elapsed:?}");
+    println!(
+        "speedup:       {:.1}x",
+        linear_elapsed.as_secs_f64() / bvh_elapsed.as_secs_f64()
+    );
+
+    let mut scene = Scene::new(Camera::new(
+        look_from,
+        Vector::default(),
+        Vector::new(0.0, 1.0, 0.0),
+        20.0,
+        16.0 / 9.0,
+        Some(0.6),
+        Some(10.0),
+    ));
+    scene.add_objects(spheres);
+
+    let start = Instant::now();
+    scene.render(320, Some(10), Some(10), NonZeroUsize::new(1), Some(42));
+    println!("320px, 10 samples render (1 thread): {:?}", start.elapsed());
+}
@@ -0,0 +1,218 @@
+use std::{fmt, num::NonZeroUsize, path::PathBuf, str::FromStr};
+
+use ray_tracing_in_one_weekend::{
+    scene::output_transform::{OutputTransform, ToneMapping, TransferFunction},
+    utils::write_img_arr_to_file::ImageFormat,
+};
+
+pub const HELP: &str = "\
+Renders a scene description file into an image
+
+Usage: ray_tracing_in_one_weekend [OPTIONS] <SCENE>
+
+Arguments:
+  <SCENE>  Path to the scene description file
+
+Options:
+  -o, --output <PATH>      Output image path [default: <SCENE name>.<FORMAT>]
+  -f, --format <FORMAT>    Output image format: ppm, ppm-binary, png,
+                           pfm, exr (high dynamic range)
+                           [default: output path's extension or ppm]
+  -W, --width <PX>         Image width (overrides the scene's render settings) [default: 480]
+  -H, --height <PX>        Image height (changes the camera's aspect ratio if width is set too)
+  -s, --samples <N>        Samples (antialiasing iterations) per pixel
+  -d, --max-depth <N>      Maximal number of ray bounces
+  -t, --threads <N>        Number of render threads [default: number of CPUs]
+      --seed <N>           Random seed making the render reproducible [default: random]
+      --exposure <EV>      Exposure adjustment in stops [default: 0]
+      --tone-mapping <OP>  Tone mapping operator: clamp, reinhard, aces [default: clamp]
+      --transfer <FN>      Transfer function: linear, srgb, gamma, gamma:<G> [default: srgb]
+                           (exposure, tone mapping and transfer are ignored for pfm and exr)
+  -h, --help               Print help
+
+Exit codes:
+  0  the image is rendered
+  1  the scene cannot be loaded or the image cannot be rendered
+  2  invalid command line arguments
+";
+
+/// Invalid command line arguments
+#[derive(Debug)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Render options given in the command line (unset options fall back to the scene's settings)
+pub struct CliArgs {
+    pub scene_path: PathBuf,
+    /// Output image path with the extension of the chosen image format
+    pub output_path: PathBuf,
+    pub format: ImageFormat,
+    pub img_width: Option<u16>,
+    pub img_height: Option<u16>,
+    pub antialiasing_iters: Option<u16>,
+    pub reflection_max_iters: Option<u16>,
+    pub threads_num: Option<NonZeroUsize>,
+    pub seed: Option<u64>,
+    pub output_transform: OutputTransform,
+}
+
+pub enum CliCommand {
+    Help,
+    Render(CliArgs),
+}
+
+/// Parses the option's value checking it is positive
+fn parse_positive<T: FromStr + PartialOrd + Default>(
+    option: &str,
+    value: &str,
+) -> Result<T, CliError> {
+    value
+        .parse::<T>()
+        .ok()
+        .filter(|x| *x > T::default())
+        .ok_or_else(|| {
+            CliError(format!(
+                "invalid value `{value}` for `{option}`: expected a positive integer"
+            ))
+        })
+}
+
+/// Parses the command line arguments (without the program name)
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliCommand, CliError> {
+    let mut args = args.into_iter();
+
+    let mut scene_path: Option<PathBuf> = None;
+    let mut output_path: Option<PathBuf> = None;
+    let mut format: Option<ImageFormat> = None;
+    let mut img_width = None;
+    let mut img_height = None;
+    let mut antialiasing_iters = None;
+    let mut reflection_max_iters = None;
+    let mut threads_num = None;
+    let mut seed = None;
+    let mut output_transform = OutputTransform::default();
+
+    while let Some(arg) = args.next() {
+        // both `--option value` and `--option=value` forms are accepted
+        let (option, inline_value) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => {
+                (option.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+
+        let mut get_value = || -> Result<String, CliError> {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError(format!("missing value for `{option}`")))
+        };
+
+        match option.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-o" | "--output" => output_path = Some(PathBuf::from(get_value()?)),
+            "-f" | "--format" => {
+                let value = get_value()?;
+                format = Some(
+                    ImageFormat::from_name(&value)
+                        .ok_or_else(|| CliError(format!("unsupported image format `{value}`")))?,
+                );
+            }
+            "-W" | "--width" => img_width = Some(parse_positive(&option, &get_value()?)?),
+            "-H" | "--height" => img_height = Some(parse_positive(&option, &get_value()?)?),
+            "-s" | "--samples" => {
+                antialiasing_iters = Some(parse_positive(&option, &get_value()?)?)
+            }
+            "-d" | "--max-depth" => {
+                reflection_max_iters = Some(parse_positive(&option, &get_value()?)?)
+            }
+            "-t" | "--threads" => {
+                let value = get_value()?;
+                threads_num = Some(NonZeroUsize::new(parse_positive(&option, &value)?).unwrap());
+            }
+            "--seed" => {
+                let value = get_value()?;
+                seed = Some(value.parse::<u64>().map_err(|_| {
+                    CliError(format!(
+                        "invalid value `{value}` for `{option}`: expected a non-negative integer"
+                    ))
+                })?);
+            }
+            "--exposure" => {
+                let value = get_value()?;
+                output_transform.exposure = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|exposure| exposure.is_finite())
+                    .ok_or_else(|| {
+                        CliError(format!(
+                            "invalid value `{value}` for `{option}`: expected a number"
+                        ))
+                    })?;
+            }
+            "--tone-mapping" => {
+                let value = get_value()?;
+                output_transform.tone_mapping = ToneMapping::from_name(&value)
+                    .ok_or_else(|| CliError(format!("unknown tone mapping operator `{value}`")))?;
+            }
+            "--transfer" => {
+                let value = get_value()?;
+                output_transform.transfer_function = TransferFunction::from_name(&value)
+                    .ok_or_else(|| CliError(format!("unknown transfer function `{value}`")))?;
+            }
+            _ if option.starts_with('-') && option.len() > 1 => {
+                return Err(CliError(format!("unknown option `{option}`")));
+            }
+            _ if scene_path.is_none() => scene_path = Some(PathBuf::from(arg)),
+            _ => return Err(CliError(format!("unexpected argument `{arg}`"))),
+        }
+    }
+
+    let scene_path = scene_path.ok_or_else(|| CliError("missing scene file path".to_string()))?;
+
+    let output_format = output_path.as_deref().and_then(ImageFormat::from_path);
+    let format = match (format, output_format) {
+        (Some(format), Some(output_format)) if format.extension() != output_format.extension() => {
+            return Err(CliError(format!(
+                "output path's extension does not match the `{}` format",
+                format.name()
+            )));
+        }
+        (Some(format), _) | (None, Some(format)) => format,
+        (None, None) => ImageFormat::Ppm,
+    };
+
+    let output_path = match output_path {
+        Some(output_path) if output_path.extension().is_none() => {
+            output_path.with_extension(format.extension())
+        }
+        Some(output_path) => output_path,
+        None => PathBuf::from(scene_path.file_stem().unwrap_or_default())
+            .with_extension(format.extension()),
+    };
+
+    if ImageFormat::from_path(&output_path).is_none() {
+        return Err(CliError(format!(
+            "unsupported output image extension in `{}`",
+            output_path.display()
+        )));
+    }
+
+    Ok(CliCommand::Render(CliArgs {
+        scene_path,
+        output_path,
+        format,
+        img_width,
+        img_height,
+        antialiasing_iters,
+        reflection_max_iters,
+        threads_num,
+        seed,
+        output_transform,
+    }))
+}
@@ -0,0 +1,3 @@
+pub mod scene;
+pub mod utils;
+pub mod vector;
@@ -1,47 +1,103 @@
-mod scene;
-mod utils;
-mod vector;
+mod cli;
 
-use std::path::Path;
+use std::{env, process::ExitCode, time::Instant};
 
-use scene::{
-    camera::Camera,
-    scene_objects::{sphere::Sphere, SceneObject},
-    Scene,
+use cli::{CliArgs, CliCommand};
+use ray_tracing_in_one_weekend::{
+    scene::loader::load_scene_from_file,
+    utils::write_img_arr_to_file::write_framebuffer_to_file_with_format,
 };
-use utils::write_img_arr_to_file::write_img_arr_to_file;
-use vector::Vector;
-
-const IMAGES_DIR: &str = "./src/images";
-
-fn main() {
-    let camera = Camera::default();
-
-    let mut scene = Scene::new(camera);
-
-    let scene_objects: Vec<Box<dyn SceneObject>> = vec![
-        Box::new(Sphere::new(
-            Vector::new(0.0, 0.0, -1.0),
-            0.3,
-            [255, 255, 255],
-            Some(1.0),
-        )),
-        Box::new(Sphere::new(
-            Vector::new(0.0, -100.3, -1.0),
-            100.0,
-            [255, 255, 255],
-            Some(1.0),
-        )),
-    ];
-    scene.add_objects(scene_objects);
-
-    let img_width = 480;
-    let antialiasing_iters = Some(100);
-    let reflection_max_iters = Some(50);
-
-    if let Some(render_result) = scene.render(img_width, antialiasing_iters, reflection_max_iters) {
-        let white_sphere_path: &Path = &Path::new(IMAGES_DIR).join("diffuse_sphere.ppm");
-
-        write_img_arr_to_file(white_sphere_path, render_result);
+
+/// Image width used if neither the command line nor the scene file sets it
+const DEFAULT_IMG_WIDTH: u16 = 480;
+
+fn main() -> ExitCode {
+    match cli::parse_args(env::args().skip(1)) {
+        Ok(CliCommand::Help) => {
+            print!("{}", cli::HELP);
+            ExitCode::SUCCESS
+        }
+        Ok(CliCommand::Render(args)) => render(args),
+        Err(e) => {
+            eprintln!("error: {e}\n\nFor more information, try '--help'");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn render(args: CliArgs) -> ExitCode {
+    let mut scene_description = match load_scene_from_file(&args.scene_path) {
+        Ok(scene_description) => scene_description,
+        Err(e) => {
+            eprintln!(
+                "error: couldn't load scene {}: {e}",
+                args.scene_path.display()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    let scene = &mut scene_description.scene;
+    let render_settings = scene_description.render_settings;
+
+    // the command line options take precedence over the scene's render settings
+    let img_width = match (args.img_width, args.img_height) {
+        (Some(img_width), Some(img_height)) => {
+            scene.set_aspect_ratio(img_width as f64 / img_height as f64);
+            img_width
+        }
+        (None, Some(img_height)) => {
+            let img_width = (img_height as f64 * scene.aspect_ratio()).round();
+            if img_width < 1.0 || img_width > u16::MAX as f64 {
+                eprintln!("error: image width for height {img_height} is out of 1..=65535 range");
+                return ExitCode::FAILURE;
+            }
+            img_width as u16
+        }
+        (Some(img_width), None) => img_width,
+        (None, None) => render_settings.img_width.unwrap_or(DEFAULT_IMG_WIDTH),
+    };
+
+    // the height is derived from the width by the camera's aspect ratio
+    let img_height = (img_width as f64 / scene.aspect_ratio()).round();
+    if img_height < 1.0 || img_height > u16::MAX as f64 {
+        eprintln!("error: image height for width {img_width} is out of 1..=65535 range");
+        return ExitCode::FAILURE;
     }
+
+    let start = Instant::now();
+
+    let Some(framebuffer) = scene.render(
+        img_width,
+        args.antialiasing_iters
+            .or(render_settings.antialiasing_iters),
+        args.reflection_max_iters
+            .or(render_settings.reflection_max_iters),
+        args.threads_num,
+        args.seed,
+    ) else {
+        eprintln!("error: couldn't render scene {}", args.scene_path.display());
+        return ExitCode::FAILURE;
+    };
+
+    if let Err(e) = write_framebuffer_to_file_with_format(
+        &args.output_path,
+        &framebuffer,
+        &args.output_transform,
+        args.format,
+    ) {
+        eprintln!(
+            "error: couldn't write image {}: {e}",
+            args.output_path.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    eprintln!(
+        "Rendered {} in {:.2?} to {}",
+        args.scene_path.display(),
+        start.elapsed(),
+        args.output_path.display()
+    );
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+use crate::vector::{Ray, Vector};
+
+/// Minimal size of a bounding box along each axis
+/// (flat objects still get a box that rays can hit)
+const AABB_MIN_SIZE: f64 = 0.0001;
+
+/// Axis-aligned bounding box
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    min: Vector,
+    max: Vector,
+}
+
+impl Aabb {
+    /// Creates bounding box with the given opposite corners `a` and `b`
+    pub fn new(a: Vector, b: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        let pad = |min: f64, max: f64| -> (f64, f64) {
+            let delta = (AABB_MIN_SIZE - (max - min)).max(0.0) / 2.0;
+
+            (min - delta, max + delta)
+        };
+
+        let (min_x, max_x) = pad(a.x().min(b.x()), a.x().max(b.x()));
+        let (min_y, max_y) = pad(a.y().min(b.y()), a.y().max(b.y()));
+        let (min_z, max_z) = pad(a.z().min(b.z()), a.z().max(b.z()));
+
+        Self {
+            min: Vector::new(min_x, min_y, min_z),
+            max: Vector::new(max_x, max_y, max_z),
+        }
+    }
+
+    /// Creates the smallest bounding box containing all the given points
+    ///
+    /// # Panics
+    /// Panics if `points` is empty
+    pub fn from_points(points: &[Vector]) -> Self {
+        let first = points.first().expect("Expected at least one point");
+
+        points
+            .iter()
+            .fold(Self::new(*first, *first), |aabb, point| {
+                aabb.union(&Self::new(*point, *point))
+            })
+    }
+
+    pub fn min(&self) -> Vector {
+        self.min
+    }
+
+    pub fn max(&self) -> Vector {
+        self.max
+    }
+
+    /// The smallest bounding box containing both boxes
+    pub fn union(&self, rhs: &Self) -> Self {
+        Self {
+            min: Vector::new(
+                self.min.x().min(rhs.min.x()),
+                self.min.y().min(rhs.min.y()),
+                self.min.z().min(rhs.min.z()),
+            ),
+            max: Vector::new(
+                self.max.x().max(rhs.max.x()),
+                self.max.y().max(rhs.max.y()),
+                self.max.z().max(rhs.max.z()),
+            ),
+        }
+    }
+
+    pub fn centroid(&self) -> Vector {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Index of the axis (0 for x, 1 for y, 2 for z) the box is the longest along
+    pub fn longest_axis(&self) -> usize {
+        let size = self.max - self.min;
+
+        if size.x() > size.y() && size.x() > size.z() {
+            0
+        } else if size.y() > size.z() {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let size = self.max - self.min;
+
+        2.0 * (size.x() * size.y() + size.y() * size.z() + size.z() * size.x())
+    }
+
+    /// Checks whether the given ray hits the box within `t_range` (slab method)
+    pub fn is_hit(&self, ray: &Ray, t_range: &Range<f64>) -> bool {
+        let origin = ray.origin();
+        let direction = ray.direction();
+
+        let mut t_min = t_range.start;
+        let mut t_max = t_range.end;
+
+        for axis in 0..3 {
+            let inv_direction = 1.0 / direction[axis];
+
+            let t_0 = (self.min[axis] - origin[axis]) * inv_direction;
+            let t_1 = (self.max[axis] - origin[axis]) * inv_direction;
+
+            let (t_near, t_far) = if inv_direction < 0.0 {
+                (t_1, t_0)
+            } else {
+                (t_0, t_1)
+            };
+
+            t_min = t_min.max(t_near);
+            t_max = t_max.min(t_far);
+
+            if t_max <= t_min {
+                return false;
+            }
+        }
+
+        true
+    }
+}
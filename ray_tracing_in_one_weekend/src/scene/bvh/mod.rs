@@ -0,0 +1,271 @@
+pub mod aabb;
+
+use std::ops::Range;
+
+use crate::vector::Ray;
+
+use self::aabb::Aabb;
+
+use super::scene_objects::HitRecord;
+
+/// Maximal number of primitives within a leaf node
+const MAX_LEAF_SIZE: usize = 4;
+
+/// Number of buckets the primitives' centroids are binned into to estimate the surface area heuristic
+const SAH_BUCKETS_NUM: usize = 12;
+
+/// Cost of traversing an interior node relative to the cost of a primitive intersection
+const SAH_TRAVERSAL_COST: f64 = 0.125;
+
+enum BvhNode {
+    Leaf {
+        bounding_box: Aabb,
+        /// Range within `Bvh::indices`
+        primitives: Range<usize>,
+    },
+    Interior {
+        bounding_box: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl BvhNode {
+    fn bounding_box(&self) -> &Aabb {
+        match self {
+            BvhNode::Leaf { bounding_box, .. } | BvhNode::Interior { bounding_box, .. } => {
+                bounding_box
+            }
+        }
+    }
+}
+
+/// Bounding volume hierarchy over a list of primitives (e.g. scene objects)
+/// built with the surface area heuristic.
+///
+/// The hierarchy stores only the primitives' indices so the primitives themselves
+/// are kept by the owner and are intersected through a callback
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Indices of the bounded primitives ordered so that each leaf references a contiguous range
+    indices: Vec<usize>,
+    /// Indices of the unbounded primitives (e.g. infinite planes) that are tested against every ray
+    unbounded_indices: Vec<usize>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy over the primitives with the given bounding boxes
+    /// (`None` stands for an unbounded primitive)
+    pub fn new(bounding_boxes: &[Option<Aabb>]) -> Self
+    where
+        Self: Sized,
+    {
+        let mut bounded = vec![];
+        let mut unbounded_indices = vec![];
+
+        for (i, bounding_box) in bounding_boxes.iter().enumerate() {
+            match bounding_box {
+                Some(bounding_box) => bounded.push((i, *bounding_box)),
+                None => unbounded_indices.push(i),
+            }
+        }
+
+        let mut bvh = Self {
+            nodes: vec![],
+            indices: vec![],
+            unbounded_indices,
+        };
+
+        if !bounded.is_empty() {
+            bvh.build_node(&mut bounded);
+        }
+
+        bvh
+    }
+
+    /// Recursively builds the node over the given primitives and returns its index
+    fn build_node(&mut self, primitives: &mut [(usize, Aabb)]) -> usize {
+        let bounding_box = primitives
+            .iter()
+            .skip(1)
+            .fold(primitives[0].1, |aabb, (_, primitive_box)| {
+                aabb.union(primitive_box)
+            });
+
+        if primitives.len() > MAX_LEAF_SIZE {
+            let mid = Self::find_split(primitives, &bounding_box);
+
+            // reserve the node's slot before its children are built
+            let node_index = self.nodes.len();
+            self.nodes.push(BvhNode::Leaf {
+                bounding_box,
+                primitives: 0..0,
+            });
+
+            let (left_primitives, right_primitives) = primitives.split_at_mut(mid);
+            let left = self.build_node(left_primitives);
+            let right = self.build_node(right_primitives);
+
+            self.nodes[node_index] = BvhNode::Interior {
+                bounding_box,
+                left,
+                right,
+            };
+
+            node_index
+        } else {
+            let start = self.indices.len();
+            self.indices.extend(primitives.iter().map(|(i, _)| *i));
+
+            self.nodes.push(BvhNode::Leaf {
+                bounding_box,
+                primitives: start..self.indices.len(),
+            });
+
+            self.nodes.len() - 1
+        }
+    }
+
+    /// Partitions the primitives in place along the longest axis of their centroids' bounds
+    /// at the bucket boundary with the lowest surface area heuristic cost.
+    ///
+    /// Returns the partition point
+    fn find_split(primitives: &mut [(usize, Aabb)], bounding_box: &Aabb) -> usize {
+        let centroids: Vec<_> = primitives.iter().map(|(_, b)| b.centroid()).collect();
+        let centroids_box = Aabb::from_points(&centroids);
+        let axis = centroids_box.longest_axis();
+
+        let axis_min = centroids_box.min()[axis];
+        let axis_extent = centroids_box.max()[axis] - axis_min;
+
+        // all the centroids coincide so the primitives are split in halves
+        if axis_extent <= 0.0 {
+            return primitives.len() / 2;
+        }
+
+        let get_bucket = |aabb: &Aabb| -> usize {
+            let offset = (aabb.centroid()[axis] - axis_min) / axis_extent;
+
+            ((offset * SAH_BUCKETS_NUM as f64) as usize).min(SAH_BUCKETS_NUM - 1)
+        };
+
+        let mut buckets: [(usize, Option<Aabb>); SAH_BUCKETS_NUM] = [(0, None); SAH_BUCKETS_NUM];
+        for (_, primitive_box) in primitives.iter() {
+            let bucket = &mut buckets[get_bucket(primitive_box)];
+
+            bucket.0 += 1;
+            bucket.1 = Some(
+                bucket
+                    .1
+                    .map_or(*primitive_box, |aabb| aabb.union(primitive_box)),
+            );
+        }
+
+        // cost of splitting after each bucket:
+        // traversal + sum of (child's surface area / node's surface area * child's primitives number)
+        let merge = |buckets: &[(usize, Option<Aabb>)]| -> (usize, f64) {
+            let (count, aabb) = buckets
+                .iter()
+                .fold((0, None::<Aabb>), |(count, aabb), (n, b)| {
+                    let merged = match (aabb, b) {
+                        (Some(a), Some(b)) => Some(a.union(b)),
+                        (a, b) => a.or(*b),
+                    };
+
+                    (count + n, merged)
+                });
+
+            (count, aabb.map_or(0.0, |aabb| aabb.surface_area()))
+        };
+
+        let best_split = (1..SAH_BUCKETS_NUM)
+            .filter_map(|i| {
+                let (left_count, left_area) = merge(&buckets[..i]);
+                let (right_count, right_area) = merge(&buckets[i..]);
+
+                let cost = SAH_TRAVERSAL_COST
+                    + (left_count as f64 * left_area + right_count as f64 * right_area)
+                        / bounding_box.surface_area();
+
+                (left_count > 0 && right_count > 0).then_some((i, cost))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best_split {
+            Some((bucket_split, _)) => {
+                partition(primitives, |(_, aabb)| get_bucket(aabb) < bucket_split)
+            }
+            None => primitives.len() / 2,
+        }
+    }
+
+    /// Returns the closest (by the ray parameter `t`) intersection between the given ray
+    /// and the primitives within `t_range` along with the intersected primitive's index.
+    ///
+    /// `calc_ray_intersection` intersects the ray with the primitive at the given index
+    pub fn calc_closest_intersection(
+        &self,
+        ray: &Ray,
+        t_range: Range<f64>,
+        calc_ray_intersection: impl Fn(usize, Range<f64>) -> Option<HitRecord>,
+    ) -> Option<(usize, HitRecord)> {
+        let mut closest: Option<(usize, HitRecord)> = None;
+
+        let test_primitive = |i: usize, closest: &mut Option<(usize, HitRecord)>| {
+            let t_max = closest.as_ref().map_or(t_range.end, |(_, hit)| hit.t);
+
+            if let Some(hit) = calc_ray_intersection(i, t_range.start..t_max) {
+                *closest = Some((i, hit));
+            }
+        };
+
+        for i in self.unbounded_indices.iter() {
+            test_primitive(*i, &mut closest);
+        }
+
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            let t_max = closest.as_ref().map_or(t_range.end, |(_, hit)| hit.t);
+
+            if !node.bounding_box().is_hit(ray, &(t_range.start..t_max)) {
+                continue;
+            }
+
+            match node {
+                BvhNode::Leaf { primitives, .. } => {
+                    for i in self.indices[primitives.clone()].iter() {
+                        test_primitive(*i, &mut closest);
+                    }
+                }
+                BvhNode::Interior { left, right, .. } => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+            }
+        }
+
+        closest
+    }
+}
+
+/// Reorders the slice so that the elements satisfying the predicate precede the others.
+///
+/// Returns the number of elements satisfying the predicate
+fn partition<T>(slice: &mut [T], predicate: impl Fn(&T) -> bool) -> usize {
+    let mut mid = 0;
+
+    for i in 0..slice.len() {
+        if predicate(&slice[i]) {
+            slice.swap(i, mid);
+            mid += 1;
+        }
+    }
+
+    mid
+}
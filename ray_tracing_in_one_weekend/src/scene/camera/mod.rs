@@ -12,13 +12,15 @@ pub struct Viewport {
 }
 
 impl Viewport {
-    pub fn new(viewport_width: f64, aspect_ratio: f64) -> Self
+    /// Creates viewport lying in the plane spanned by the camera basis vectors `u` and `v`
+    /// (unit length, pointing to the camera's right and up respectively)
+    pub fn new(viewport_width: f64, aspect_ratio: f64, u: &Vector, v: &Vector) -> Self
     where
         Self: Sized,
     {
         let viewport_height = viewport_width / aspect_ratio;
-        let viewport_u = Vector::new(viewport_width, 0.0, 0.0);
-        let viewport_v = Vector::new(0.0, -viewport_height, 0.0);
+        let viewport_u = u * viewport_width;
+        let viewport_v = v * (-viewport_height);
 
         Self {
             aspect_ratio,
@@ -32,49 +34,123 @@ impl Viewport {
 
 /// Base camera class
 pub struct Camera {
+    /// Distance from the camera center to the viewport
+    /// which is the plane of perfect focus
     pub focal_length: f64,
+    /// Variation angle in degrees of rays through each pixel
+    /// (the cone apex is at the viewport, its base is the camera lens disk).
+    /// Zero angle stands for a pinhole camera without defocus blur
+    pub defocus_angle: f64,
+    /// Point the camera is looking from
     pub camera_center: Vector,
+    /// Point the camera is looking at
+    pub look_at: Vector,
+    /// Camera-relative "up" direction
+    pub vup: Vector,
+    /// Vertical field of view in degrees
+    pub vfov: f64,
+    /// Camera frame basis vector (unit length) pointing to the camera's right
+    pub u: Vector,
+    /// Camera frame basis vector (unit length) pointing to the camera's up
+    pub v: Vector,
+    /// Camera frame basis vector (unit length) opposite to the view direction
+    pub w: Vector,
     pub viewport: Viewport,
 }
 
-impl Camera {
+impl Default for Camera {
     /// Default camera with:  
-    /// `focal_length` = 1  
-    /// `viewport_width` = 2 so that viewport's x coordinate is bounded by (-1, 1)  
+    /// `look_from` = (0, 0, 0)  
+    /// `look_at` = (0, 0, -1) so that `focal_length` = 1  
+    /// `vup` = (0, 1, 0)  
     /// `aspect_ratio` = 16 / 9  
-    /// `camera_center` = (0, 0, 0)
-    pub fn default() -> Self
-    where
-        Self: Sized,
-    {
-        let focal_length = 1.0;
-        let viewport_width = 2.0;
-        let aspect_ratio = 16.0 / 9.0;
-        let viewport = Viewport::new(viewport_width, aspect_ratio);
-        let camera_center = Vector::default();
+    /// `vfov` chosen so that `viewport_width` = 2 and viewport's x coordinate is bounded by (-1, 1)  
+    /// no defocus blur
+    fn default() -> Self {
+        let aspect_ratio: f64 = 16.0 / 9.0;
+        let vfov = 2.0 * (1.0 / aspect_ratio).atan().to_degrees();
 
-        Self {
-            focal_length,
-            camera_center,
-            viewport,
-        }
+        Self::new(
+            Vector::default(),
+            Vector::new(0.0, 0.0, -1.0),
+            Vector::new(0.0, 1.0, 0.0),
+            vfov,
+            aspect_ratio,
+            None,
+            None,
+        )
     }
+}
 
+impl Camera {
+    /// Creates camera placed at `look_from` and directed to `look_at`
+    /// with `vup` defining the camera's roll and the vertical field of view `vfov` in degrees
+    ///
+    /// `defocus_angle` (in degrees, 0 by default) defines the lens aperture
+    /// and `focus_dist` (the distance from `look_from` to `look_at` by default)
+    /// defines the distance to the plane of perfect focus
+    ///
+    /// # Panics
+    /// Panics if `look_from` equals `look_at`, `vup` is parallel to the view direction
+    /// or `focus_dist` is not positive
     pub fn new(
-        focal_length: f64,
+        look_from: Vector,
+        look_at: Vector,
+        vup: Vector,
+        vfov: f64,
         aspect_ratio: f64,
-        viewport_width: f64,
-        camera_center: Vector,
+        defocus_angle: Option<f64>,
+        focus_dist: Option<f64>,
     ) -> Self
     where
         Self: Sized,
     {
-        let viewport = Viewport::new(viewport_width, aspect_ratio);
+        let view_vector = look_from - look_at;
+        assert!(
+            view_vector.sq_norm() > 0.0,
+            "Camera should not look at its own center"
+        );
+
+        let focal_length = focus_dist.unwrap_or(view_vector.sq_norm().sqrt());
+        assert!(
+            focal_length > 0.0,
+            "Camera's focus distance should be positive"
+        );
+
+        // orthonormal camera frame basis
+        let w = view_vector.normalize();
+        let vup_cross_w = vup.cross(&w);
+        assert!(
+            vup_cross_w.sq_norm() > 0.0,
+            "Camera's vup should not be parallel to the view direction"
+        );
+        let u = vup_cross_w.normalize();
+        let v = w.cross(&u);
+
+        let viewport_height = 2.0 * (vfov.to_radians() / 2.0).tan() * focal_length;
+        let viewport = Viewport::new(viewport_height * aspect_ratio, aspect_ratio, &u, &v);
 
         Self {
             focal_length,
-            camera_center,
+            defocus_angle: defocus_angle.unwrap_or(0.0),
+            camera_center: look_from,
+            look_at,
+            vup,
+            vfov,
+            u,
+            v,
+            w,
             viewport,
         }
     }
+
+    /// Changes the aspect ratio of the viewport keeping the vertical field of view
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f64) {
+        self.viewport = Viewport::new(
+            self.viewport.viewport_height * aspect_ratio,
+            aspect_ratio,
+            &self.u,
+            &self.v,
+        );
+    }
 }
@@ -0,0 +1,74 @@
+use std::f64::consts::PI;
+
+use crate::{
+    scene::framebuffer::Framebuffer,
+    vector::{Ray, Vector},
+};
+
+use super::Environment;
+
+/// Environment given by a latitude-longitude (equirectangular) image:
+/// the image's columns cover the full circle around the vertical axis
+/// (the center column lies in `-z` direction) and its rows go from straight up to straight down
+pub struct EquirectangularMap {
+    image: Framebuffer,
+    /// Multiplier of the image's colors
+    strength: f64,
+}
+
+impl EquirectangularMap {
+    /// Creates the environment from the non-empty `image` of linear colors
+    /// (colors are not scaled by default)
+    pub fn new(image: Framebuffer, strength: Option<f64>) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(!image.is_empty(), "Environment map should not be empty");
+
+        Self {
+            image,
+            strength: strength.unwrap_or(1.0),
+        }
+    }
+
+    /// Returns the bilinearly interpolated color at the image coordinates (`u`, `v`)
+    /// given in pixels (wraps around horizontally and clamps vertically)
+    fn sample(&self, u: f64, v: f64) -> Vector {
+        let width = self.image.width() as i64;
+        let height = self.image.height() as i64;
+
+        // pixel centers lie at half-integer coordinates
+        let u = u - 0.5;
+        let v = (v - 0.5).clamp(0.0, (height - 1) as f64);
+        let (x0, y0) = (u.floor(), v.floor());
+        let (tx, ty) = (u - x0, v - y0);
+
+        let get_pixel = |x: i64, y: i64| -> Vector {
+            *self.image.get_pixel(
+                x.rem_euclid(width) as usize,
+                y.clamp(0, height - 1) as usize,
+            )
+        };
+
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top = get_pixel(x0, y0) * (1.0 - tx) + get_pixel(x0 + 1, y0) * tx;
+        let bottom = get_pixel(x0, y0 + 1) * (1.0 - tx) + get_pixel(x0 + 1, y0 + 1) * tx;
+
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+impl Environment for EquirectangularMap {
+    fn get_color(&self, ray: &Ray) -> Vector {
+        let direction = ray.direction();
+
+        // azimuth is measured from `-z` direction, polar angle from `+y` direction
+        let azimuth = direction.x().atan2(-direction.z());
+        let polar_angle = direction.y().clamp(-1.0, 1.0).acos();
+
+        let u = (0.5 + azimuth / (2.0 * PI)) * self.image.width() as f64;
+        let v = polar_angle / PI * self.image.height() as f64;
+
+        self.sample(u, v) * self.strength
+    }
+}
@@ -0,0 +1,12 @@
+pub mod equirectangular_map;
+pub mod solid;
+pub mod vertical_gradient;
+
+use crate::vector::{Ray, Vector};
+
+/// Describes the light coming from infinitely far away:
+/// the rays that do not hit any object take their color from the environment
+pub trait Environment: Send + Sync {
+    /// Returns the color (radiance) arriving along the `ray` from the environment
+    fn get_color(&self, ray: &Ray) -> Vector;
+}
@@ -0,0 +1,23 @@
+use crate::vector::{Ray, Vector};
+
+use super::Environment;
+
+/// Environment of the same color in every direction
+pub struct Solid {
+    color: Vector,
+}
+
+impl Solid {
+    pub fn new(color: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self { color }
+    }
+}
+
+impl Environment for Solid {
+    fn get_color(&self, _ray: &Ray) -> Vector {
+        self.color
+    }
+}
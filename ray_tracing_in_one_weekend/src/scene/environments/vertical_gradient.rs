@@ -0,0 +1,33 @@
+use crate::vector::{Ray, Vector};
+
+use super::Environment;
+
+/// Linear blend from `bottom` color for the rays pointing down to `top` color for the rays pointing up
+pub struct VerticalGradient {
+    bottom: Vector,
+    top: Vector,
+}
+
+impl VerticalGradient {
+    pub fn new(bottom: Vector, top: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self { bottom, top }
+    }
+}
+
+impl Default for VerticalGradient {
+    /// White to light blue sky gradient
+    fn default() -> Self {
+        Self::new(Vector::new(1.0, 1.0, 1.0), Vector::new(0.5, 0.7, 1.0))
+    }
+}
+
+impl Environment for VerticalGradient {
+    fn get_color(&self, ray: &Ray) -> Vector {
+        let a = 0.5 * (ray.direction().y() + 1.0);
+
+        self.bottom * (1.0 - a) + self.top * a
+    }
+}
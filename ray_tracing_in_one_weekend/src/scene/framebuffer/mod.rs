@@ -0,0 +1,95 @@
+use crate::vector::Vector;
+
+use super::{
+    output_transform::{OutputTransform, TransferFunction},
+    Color,
+};
+
+/// High dynamic range image: linear colors (not limited to 0.0-1.0 range) stored row by row
+/// from the top row to the bottom one
+#[derive(Clone, Debug)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vector>,
+}
+
+impl Framebuffer {
+    /// Creates black `width` x `height` image
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            width,
+            height,
+            pixels: vec![Vector::default(); width * height],
+        }
+    }
+
+    /// Creates the image from non-ragged 8-bit image data (rows of `[r, g, b]` pixels)
+    /// decoding the colors encoded with `transfer_function` into linear ones
+    pub fn from_img_arr(img_arr: &[Vec<Color>], transfer_function: &TransferFunction) -> Self
+    where
+        Self: Sized,
+    {
+        let width = img_arr.first().map_or(0, Vec::len);
+        let decode = |x: u8| -> f64 { transfer_function.decode(x as f64 / 255.0) };
+
+        Self {
+            width,
+            height: img_arr.len(),
+            pixels: img_arr
+                .iter()
+                .flatten()
+                .map(|[r, g, b]| Vector::new(decode(*r), decode(*g), decode(*b)))
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> &Vector {
+        &self.pixels[y * self.width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Vector) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    pub fn row(&self, y: usize) -> &[Vector] {
+        &self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [Vector] {
+        &mut self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the rows from the top row to the bottom one
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[Vector]> {
+        // `max` keeps `chunks_exact` from panicking on zero width images (which have no rows)
+        self.pixels.chunks_exact(self.width.max(1))
+    }
+
+    /// Converts the image into 8-bit image data (rows of `[r, g, b]` pixels)
+    /// with the given `output_transform` (exposure, tone mapping and transfer function)
+    pub fn to_img_arr(&self, output_transform: &OutputTransform) -> Vec<Vec<Color>> {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|color| output_transform.to_color(color))
+                    .collect()
+            })
+            .collect()
+    }
+}
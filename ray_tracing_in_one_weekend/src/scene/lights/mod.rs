@@ -0,0 +1,40 @@
+pub mod point_light;
+pub mod quad_light;
+pub mod sphere_light;
+
+use rand::RngCore;
+
+use crate::vector::Vector;
+
+use super::scene_objects::SceneObject;
+
+/// Point of a light chosen to illuminate some point of a scene
+pub struct LightSample {
+    /// Direction (unit length) from the lit point to the sampled point
+    pub direction: Vector,
+    /// Distance from the lit point to the sampled point
+    pub distance: f64,
+    /// Color (radiance) arriving at the lit point from the sampled point
+    /// if nothing blocks the light
+    pub radiance: Vector,
+    /// Probability density (per unit solid angle) of choosing `direction`
+    /// or `None` if the light is located at a single point
+    pub pdf: Option<f64>,
+}
+
+/// Light that is sampled directly: every diffuse surface checks whether it sees the light
+pub trait Light: Send + Sync {
+    /// Chooses a random point of the light to illuminate the given `point`.
+    ///
+    /// Returns `None` if the chosen point does not emit towards `point`
+    fn sample(&self, point: &Vector, rng: &mut dyn RngCore) -> Option<LightSample>;
+
+    /// Returns the probability density (per unit solid angle) of `sample` choosing `direction`
+    /// (unit length) from the given `point`
+    /// (0.0 if the light is not in that direction or is located at a single point)
+    fn pdf(&self, point: &Vector, direction: &Vector) -> f64;
+
+    /// Returns the scene object representing the light's emitting surface
+    /// or `None` if the light is located at a single point
+    fn get_object(&self) -> Option<Box<dyn SceneObject>>;
+}
@@ -0,0 +1,51 @@
+use rand::RngCore;
+
+use crate::{scene::scene_objects::SceneObject, vector::Vector};
+
+use super::{Light, LightSample};
+
+/// Light emitting equally in every direction from a single point
+///
+/// Point lights are invisible: they light the surfaces but cannot be seen or hit by rays
+pub struct PointLight {
+    position: Vector,
+    /// Emitted color (radiant intensity), the light gets dimmer with the squared distance
+    intensity: Vector,
+}
+
+impl PointLight {
+    pub fn new(position: Vector, intensity: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            position,
+            intensity,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn sample(&self, point: &Vector, _rng: &mut dyn RngCore) -> Option<LightSample> {
+        let to_light = self.position - *point;
+        let sq_distance = to_light.sq_norm();
+        if sq_distance == 0.0 {
+            return None;
+        }
+
+        Some(LightSample {
+            direction: to_light.normalize(),
+            distance: sq_distance.sqrt(),
+            radiance: self.intensity / sq_distance,
+            pdf: None,
+        })
+    }
+
+    fn pdf(&self, _point: &Vector, _direction: &Vector) -> f64 {
+        0.0
+    }
+
+    fn get_object(&self) -> Option<Box<dyn SceneObject>> {
+        None
+    }
+}
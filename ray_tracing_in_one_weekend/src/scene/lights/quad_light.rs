@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use rand::{Rng, RngCore};
+
+use crate::{
+    scene::{
+        materials::diffuse_light::DiffuseLight,
+        render::RAY_T_MIN,
+        scene_objects::{quad::Quad, RayToObjectHandler, SceneObject},
+    },
+    vector::{Ray, Vector},
+};
+
+use super::{Light, LightSample};
+
+/// Parallelogram (e.g. rectangular) light spanned by the edges `u` and `v` from the `corner`
+/// emitting from the side `u x v` points to
+pub struct QuadLight {
+    corner: Vector,
+    u: Vector,
+    v: Vector,
+    /// Emitted color (radiance)
+    emit: Vector,
+    /// Normal vector (unit length) of the emitting side
+    normal_vector: Vector,
+    area: f64,
+    /// Emitting surface
+    quad: Quad,
+}
+
+impl QuadLight {
+    /// Creates the light with non-parallel edges `u` and `v`
+    pub fn new(corner: Vector, u: Vector, v: Vector, emit: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        let n = u.cross(&v);
+
+        Self {
+            corner,
+            u,
+            v,
+            emit,
+            normal_vector: n.normalize(),
+            area: n.sq_norm().sqrt(),
+            quad: Quad::new(corner, u, v, Arc::new(DiffuseLight::new(emit))),
+        }
+    }
+
+    /// Converts the uniform density (per unit area) of the surface point
+    /// at `distance` with the given cosine of the emission angle into the density per unit solid angle
+    fn get_solid_angle_pdf(&self, distance: f64, cos_theta: f64) -> f64 {
+        distance.powi(2) / (cos_theta * self.area)
+    }
+}
+
+impl Light for QuadLight {
+    fn sample(&self, point: &Vector, rng: &mut dyn RngCore) -> Option<LightSample> {
+        let to_light = self.corner + self.u * rng.gen::<f64>() + self.v * rng.gen::<f64>() - *point;
+        let distance = to_light.sq_norm().sqrt();
+        let direction = to_light / distance;
+
+        let cos_theta = -self.normal_vector.dot(&direction);
+        if cos_theta <= 0.0 {
+            return None;
+        }
+
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: self.emit,
+            pdf: Some(self.get_solid_angle_pdf(distance, cos_theta)),
+        })
+    }
+
+    fn pdf(&self, point: &Vector, direction: &Vector) -> f64 {
+        let ray = Ray::new(*point, *direction);
+
+        match self
+            .quad
+            .calc_ray_intersection(&ray, RAY_T_MIN..f64::INFINITY)
+        {
+            Some(hit) if hit.is_front_face => {
+                self.get_solid_angle_pdf(hit.t, -self.normal_vector.dot(&ray.direction()))
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn get_object(&self) -> Option<Box<dyn SceneObject>> {
+        Some(Box::new(self.quad.clone()))
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::{
+    scene::{
+        materials::diffuse_light::DiffuseLight,
+        scene_objects::{sphere::Sphere, SceneObject},
+    },
+    vector::{
+        sampling::{sample_uniform_cone, uniform_cone_pdf},
+        Vector,
+    },
+};
+
+use super::{Light, LightSample};
+
+/// Spherical light emitting from its outer surface
+pub struct SphereLight {
+    center: Vector,
+    radius: f64,
+    /// Emitted color (radiance)
+    emit: Vector,
+    /// Emitting surface
+    sphere: Sphere,
+}
+
+impl SphereLight {
+    pub fn new(center: Vector, radius: f64, emit: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            center,
+            radius,
+            emit,
+            sphere: Sphere::new(center, radius, Arc::new(DiffuseLight::new(emit))),
+        }
+    }
+
+    /// Returns the direction (unit length) from the `point` to the center,
+    /// the distance to the center and the cosine of the half-angle of the cone
+    /// the sphere is seen within from the point
+    /// or `None` if the point lies inside the sphere (its inner side emits nothing)
+    fn get_visible_cone(&self, point: &Vector) -> Option<(Vector, f64, f64)> {
+        let to_center = self.center - *point;
+        let sq_distance = to_center.sq_norm();
+        if sq_distance <= self.radius.powi(2) {
+            return None;
+        }
+
+        let distance = sq_distance.sqrt();
+        let cos_theta_max = (1.0 - self.radius.powi(2) / sq_distance).max(0.0).sqrt();
+
+        Some((to_center / distance, distance, cos_theta_max))
+    }
+}
+
+impl Light for SphereLight {
+    fn sample(&self, point: &Vector, rng: &mut dyn RngCore) -> Option<LightSample> {
+        // uniformly distributed direction within the cone of the sphere's visible side
+        // (sampling the whole surface would waste the points on its far side)
+        let (axis, center_distance, cos_theta_max) = self.get_visible_cone(point)?;
+        let direction = sample_uniform_cone(&axis, cos_theta_max, rng);
+
+        // distance to the near intersection with the sphere
+        let cos_theta = axis.dot(&direction);
+        let sq_sin_theta = 1.0 - cos_theta.powi(2);
+        let distance = center_distance * cos_theta
+            - (self.radius.powi(2) - center_distance.powi(2) * sq_sin_theta)
+                .max(0.0)
+                .sqrt();
+
+        Some(LightSample {
+            direction,
+            distance,
+            radiance: self.emit,
+            pdf: Some(uniform_cone_pdf(cos_theta_max)),
+        })
+    }
+
+    fn pdf(&self, point: &Vector, direction: &Vector) -> f64 {
+        match self.get_visible_cone(point) {
+            Some((axis, _, cos_theta_max)) if axis.dot(direction) >= cos_theta_max => {
+                uniform_cone_pdf(cos_theta_max)
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn get_object(&self) -> Option<Box<dyn SceneObject>> {
+        Some(Box::new(self.sphere.clone()))
+    }
+}
@@ -0,0 +1,177 @@
+use crate::vector::Vector;
+
+use super::error::SceneLoadError;
+
+/// Value of a directive's field that can be parsed from its text representation
+pub trait FieldValue: Sized {
+    /// Parses the value or returns a description of what is expected
+    fn parse_field(value: &str) -> Result<Self, String>;
+}
+
+impl FieldValue for f64 {
+    fn parse_field(value: &str) -> Result<Self, String> {
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|x| x.is_finite())
+            .ok_or_else(|| format!("expected a number, got `{value}`"))
+    }
+}
+
+impl FieldValue for u16 {
+    fn parse_field(value: &str) -> Result<Self, String> {
+        value
+            .parse::<u16>()
+            .map_err(|_| format!("expected an integer in 0..=65535 range, got `{value}`"))
+    }
+}
+
+impl FieldValue for bool {
+    /// Parses `true`/`false` or `1`/`0`
+    fn parse_field(value: &str) -> Result<Self, String> {
+        match value {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(format!("expected `true` or `false`, got `{value}`")),
+        }
+    }
+}
+
+impl FieldValue for Vector {
+    /// Parses comma separated coordinates: `x,y,z`
+    fn parse_field(value: &str) -> Result<Self, String> {
+        let coords = value
+            .split(',')
+            .map(|x| x.trim().parse::<f64>().ok().filter(|x| x.is_finite()))
+            .collect::<Option<Vec<f64>>>();
+
+        match coords.as_deref() {
+            Some([x, y, z]) => Ok(Vector::new(*x, *y, *z)),
+            _ => Err(format!("expected a vector `x,y,z`, got `{value}`")),
+        }
+    }
+}
+
+impl FieldValue for String {
+    fn parse_field(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+/// Single line of a scene description:
+/// `kind [argument ...] [field=value ...]`
+///
+/// Arguments are positional and fields are named.
+/// Every argument and field must be consumed by the loader, otherwise it is reported as unknown
+pub struct Directive<'a> {
+    line: usize,
+    kind: &'a str,
+    args: Vec<(&'a str, bool)>,
+    fields: Vec<(&'a str, &'a str, bool)>,
+}
+
+impl<'a> Directive<'a> {
+    /// Parses the line's tokens (comments are expected to be stripped).
+    ///
+    /// Returns `None` for an empty line
+    pub fn parse(line: usize, source: &'a str) -> Result<Option<Self>, SceneLoadError> {
+        let mut tokens = source.split_whitespace();
+
+        let Some(kind) = tokens.next() else {
+            return Ok(None);
+        };
+
+        let mut directive = Self {
+            line,
+            kind,
+            args: vec![],
+            fields: vec![],
+        };
+
+        for token in tokens {
+            match token.split_once('=') {
+                Some(("", _)) => {
+                    return Err(directive.error(None, format!("missing field name in `{token}`")));
+                }
+                Some((key, value)) => {
+                    if directive.fields.iter().any(|(k, _, _)| *k == key) {
+                        return Err(directive.error(Some(key), "duplicate field"));
+                    }
+
+                    directive.fields.push((key, value, false));
+                }
+                None if directive.fields.is_empty() => directive.args.push((token, false)),
+                None => {
+                    return Err(directive.error(
+                        None,
+                        format!("unexpected argument `{token}` after named fields"),
+                    ));
+                }
+            }
+        }
+
+        Ok(Some(directive))
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn kind(&self) -> &'a str {
+        self.kind
+    }
+
+    /// Creates an error pointing to this directive's line and the given field
+    pub fn error(&self, field: Option<&str>, message: impl Into<String>) -> SceneLoadError {
+        SceneLoadError::syntax(self.line, field, message)
+    }
+
+    /// Returns the positional argument at `index` which is referred to as `name` in errors
+    pub fn get_arg(&mut self, index: usize, name: &str) -> Result<&'a str, SceneLoadError> {
+        match self.args.get_mut(index) {
+            Some((arg, is_used)) => {
+                *is_used = true;
+                Ok(*arg)
+            }
+            None => Err(self.error(
+                Some(name),
+                format!("missing argument (`{} ...`)", self.kind),
+            )),
+        }
+    }
+
+    /// Returns the parsed value of the field with the given key if the field is present
+    pub fn get_optional<T: FieldValue>(&mut self, key: &str) -> Result<Option<T>, SceneLoadError> {
+        let line = self.line;
+
+        match self.fields.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, value, is_used)) => {
+                *is_used = true;
+
+                T::parse_field(value)
+                    .map(Some)
+                    .map_err(|message| SceneLoadError::syntax(line, Some(key), message))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the parsed value of the required field with the given key
+    pub fn get<T: FieldValue>(&mut self, key: &str) -> Result<T, SceneLoadError> {
+        self.get_optional(key)?
+            .ok_or_else(|| self.error(Some(key), "missing required field"))
+    }
+
+    /// Checks that all the arguments and fields have been consumed
+    pub fn finish(self) -> Result<(), SceneLoadError> {
+        if let Some((arg, _)) = self.args.iter().find(|(_, is_used)| !is_used) {
+            return Err(self.error(None, format!("unexpected argument `{arg}`")));
+        }
+
+        if let Some((key, _, _)) = self.fields.iter().find(|(_, _, is_used)| !is_used) {
+            return Err(self.error(Some(key), format!("unknown field for `{}`", self.kind)));
+        }
+
+        Ok(())
+    }
+}
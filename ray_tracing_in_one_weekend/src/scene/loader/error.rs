@@ -0,0 +1,58 @@
+use std::{error::Error, fmt, io};
+
+/// Error occurred while loading a scene description
+#[derive(Debug)]
+pub enum SceneLoadError {
+    /// The scene file cannot be read
+    Io(io::Error),
+    /// Invalid scene description at the given line (starting from 1)
+    /// and optionally at the given field of the line
+    Syntax {
+        line: usize,
+        field: Option<String>,
+        message: String,
+    },
+}
+
+impl SceneLoadError {
+    pub fn syntax(line: usize, field: Option<&str>, message: impl Into<String>) -> Self {
+        SceneLoadError::Syntax {
+            line,
+            field: field.map(String::from),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SceneLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneLoadError::Io(e) => write!(f, "couldn't read scene file: {e}"),
+            SceneLoadError::Syntax {
+                line,
+                field: Some(field),
+                message,
+            } => write!(f, "line {line}, field `{field}`: {message}"),
+            SceneLoadError::Syntax {
+                line,
+                field: None,
+                message,
+            } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl Error for SceneLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SceneLoadError::Io(e) => Some(e),
+            SceneLoadError::Syntax { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for SceneLoadError {
+    fn from(e: io::Error) -> Self {
+        SceneLoadError::Io(e)
+    }
+}
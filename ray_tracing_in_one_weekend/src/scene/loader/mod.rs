@@ -0,0 +1,988 @@
+//! Loader of text scene descriptions.
+//!
+//! A scene file consists of directives, one per line:
+//! `kind [argument ...] [field=value ...]`.
+//! Vectors and colors (0.0-1.0 range for each color component) are written as `x,y,z`,
+//! everything after `#` is a comment.
+//!
+//! ```text
+//! camera look_from=0,0,0 look_at=0,0,-1 vfov=60 [vup=0,1,0] [aspect_ratio=1.7778]
+//!        [defocus_angle=0] [focus_dist=1]
+//! render [img_width=480] [antialiasing_iters=100] [reflection_max_iters=50]
+//! background solid color=0,0,0
+//! background gradient bottom=1,1,1 top=0.5,0.7,1
+//! background image path=<file.pfm|file.ppm> [strength=1]
+//! texture <name> solid color=0.5,0.5,0.5
+//! texture <name> checker even=<color|texture> odd=<color|texture> [scale=1]
+//! texture <name> image path=<file.png|file.ppm|file.pfm> [wrap=repeat|clamp|mirror]
+//!         [transfer=srgb]
+//! texture <name> noise|turbulence|marble [scale=1] [color=1,1,1]
+//! material <name> lambertian albedo=<color|texture>
+//! material <name> metal albedo=0.8,0.8,0.8 [fuzz=0]
+//! material <name> dielectric refraction_index=1.5
+//! material <name> diffuse_light emit=4,4,4
+//! material <name> <type> <the type's fields> normal_map|bump_map=<texture> [map_strength=1]
+//! sphere center=0,0,-1 radius=0.5 material=<name>
+//! quad corner=0,0,0 u=1,0,0 v=0,1,0 material=<name>
+//! plane point=0,0,0 normal=0,1,0 material=<name>
+//! disk center=0,0,0 normal=0,1,0 radius=1 material=<name>
+//! triangle a=0,0,0 b=1,0,0 c=0,1,0 material=<name>
+//! box min=0,0,0 max=1,1,1 material=<name>
+//! cylinder base=0,0,0 axis=0,1,0 radius=0.5 height=1 [capped=true] material=<name>
+//! cone base=0,0,0 axis=0,1,0 radius=0.5 height=1 [capped=true] material=<name>
+//! torus center=0,0,0 axis=0,1,0 major_radius=1 minor_radius=0.25 material=<name>
+//! mesh path=<file.obj> [material=<name>]
+//! solid <name> sphere|box|cylinder|cone|torus <the object's fields except material>
+//! solid <name> union|intersection|difference a=<solid> b=<solid>
+//! csg union|intersection|difference a=<solid> b=<solid> material=<name>
+//! object <name> <object's kind> <the object's fields>
+//! instance object=<name> [material=<name>]
+//! light point position=0,1,0 intensity=10,10,10
+//! light sphere center=0,1,0 radius=0.5 emit=4,4,4
+//! light quad corner=0,1,0 u=1,0,0 v=0,0,1 emit=4,4,4
+//! ```
+//!
+//! Emitted colors of lights may exceed 1.0. The outer side of a quad is the one `u x v` points to,
+//! the outer side of a triangle is the one its vertices are seen counterclockwise from.
+//! Lights are sampled directly which makes them converge much faster
+//! than the objects of `diffuse_light` material (point lights are invisible).
+//!
+//! Solids are not rendered on their own, they are combined by constructive solid geometry
+//! into `csg` objects made of a single material (uncapped cylinders and cones are not solids).
+//!
+//! Objects (including `object` definitions but not solids) and instances may be transformed
+//! by optional `scale=1,1,1` (non-zero factors), `rotate=0,0,0` (angles in degrees around x, y
+//! and z axes) and `translate=0,0,0` fields applied in this order.
+//! Defined objects are not rendered on their own, they are placed into the scene by instances
+//! sharing their geometry (an instance may replace the object's material).
+//!
+//! Textures, materials, solids and objects must be defined before the directives referring to them.
+//! Relative file paths are resolved against the scene file's directory
+//! (or the current directory for the scenes parsed from a string).
+//!
+//! The image background is an equirectangular environment map
+//! (".ppm" and ".png" colors are expected to be sRGB encoded).
+//! Image textures are mapped onto the objects' surface coordinates,
+//! checker and noise textures fill the space around the objects.
+//! Texture's ".ppm" and ".png" colors are decoded by the `transfer` function
+//! (see `--transfer` option), data images such as normal maps are usually `linear`.
+//! Meshes are loaded from Wavefront OBJ models (see `obj` module) with their MTL materials,
+//! `material` replaces the grey lambertian material of the faces without an MTL material
+//!
+//! Any material may get the surface detail of a tangent-space normal map
+//! (the colors encode the normal's coordinates along the surface's `u`, `v` and the normal)
+//! or a bump map (the grey levels are the heights above the surface), `map_strength` scales
+//! the detail. The maps follow the objects' surface coordinates, bump maps of noise textures
+//! follow the space around the objects. The slopes of bump maps are measured per unit of
+//! the surface coordinates (per unit of length for noise textures), so image bump maps
+//! give the same detail on objects of any size.
+
+pub mod directive;
+pub mod error;
+pub mod obj;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    utils::read_img_arr_from_file::{
+        read_framebuffer_from_file, read_framebuffer_from_file_with_transfer,
+    },
+    vector::{mat4::Mat4, Ray, Vector},
+};
+
+use self::{
+    directive::{Directive, FieldValue},
+    error::SceneLoadError,
+    obj::load_obj_from_file,
+};
+
+use super::{
+    camera::Camera,
+    environments::{
+        equirectangular_map::EquirectangularMap, solid::Solid, vertical_gradient::VerticalGradient,
+        Environment,
+    },
+    lights::{point_light::PointLight, quad_light::QuadLight, sphere_light::SphereLight, Light},
+    materials::{
+        dielectric::Dielectric,
+        diffuse_light::DiffuseLight,
+        lambertian::Lambertian,
+        metal::Metal,
+        normal_mapped::{NormalMapped, SurfaceMap},
+        Material,
+    },
+    output_transform::TransferFunction,
+    scene_objects::{
+        axis_aligned_box::AxisAlignedBox,
+        cone::Cone,
+        csg::{Csg, CsgOperation},
+        cylinder::Cylinder,
+        disk::Disk,
+        plane::Plane,
+        quad::Quad,
+        sphere::Sphere,
+        torus::Torus,
+        transformed::Transformed,
+        triangle::Triangle,
+        SceneObject,
+    },
+    textures::{
+        checker::Checker,
+        image_texture::{ImageTexture, WrapMode},
+        noise::{NoisePattern, NoiseTexture},
+        solid_color::SolidColor,
+        Texture,
+    },
+    Scene,
+};
+
+/// Render settings described in a scene file
+/// (unset settings fall back to the renderer's defaults)
+#[derive(Default, Clone, Copy, Debug)]
+pub struct RenderSettings {
+    pub img_width: Option<u16>,
+    pub antialiasing_iters: Option<u16>,
+    pub reflection_max_iters: Option<u16>,
+}
+
+/// Scene loaded from a scene description with its render settings
+pub struct SceneDescription {
+    pub scene: Scene,
+    pub render_settings: RenderSettings,
+}
+
+/// Loads the scene description from the file at `path`
+pub fn load_scene_from_file(path: &Path) -> Result<SceneDescription, SceneLoadError> {
+    let source = fs::read_to_string(path)?;
+
+    parse_scene_in_dir(&source, path.parent().unwrap_or(Path::new("")))
+}
+
+/// Parses the scene description from its text representation
+pub fn parse_scene(source: &str) -> Result<SceneDescription, SceneLoadError> {
+    parse_scene_in_dir(source, Path::new(""))
+}
+
+/// Parses the scene description resolving relative file paths against `base_dir`
+fn parse_scene_in_dir(source: &str, base_dir: &Path) -> Result<SceneDescription, SceneLoadError> {
+    let mut loader = SceneLoader {
+        base_dir: base_dir.to_path_buf(),
+        ..Default::default()
+    };
+
+    for (i, line) in source.lines().enumerate() {
+        let line_without_comment = line.split('#').next().unwrap_or_default();
+
+        if let Some(directive) = Directive::parse(i + 1, line_without_comment)? {
+            loader.load_directive(directive)?;
+        }
+    }
+
+    Ok(loader.finish())
+}
+
+/// Loads the solid object of the given kind (the kinds of objects enclosing a volume)
+fn load_solid(
+    kind: &str,
+    directive: &mut Directive,
+    material: Arc<dyn Material>,
+) -> Result<Box<dyn SceneObject>, SceneLoadError> {
+    let object: Box<dyn SceneObject> = match kind {
+        "sphere" => {
+            let center = directive.get::<Vector>("center")?;
+            let radius = directive.get::<f64>("radius")?;
+            if radius <= 0.0 {
+                return Err(directive.error(Some("radius"), "radius should be positive"));
+            }
+
+            Box::new(Sphere::new(center, radius, material))
+        }
+        "box" => {
+            let min = directive.get::<Vector>("min")?;
+            let max = directive.get::<Vector>("max")?;
+            if (0..3).any(|axis| min[axis] > max[axis]) {
+                return Err(directive.error(
+                    Some("max"),
+                    "max should not be less than min along any axis",
+                ));
+            }
+
+            Box::new(AxisAlignedBox::new(min, max, material))
+        }
+        "cylinder" | "cone" => {
+            let base_center = directive.get::<Vector>("base")?;
+            let axis = directive.get::<Vector>("axis")?;
+            if axis.sq_norm() == 0.0 {
+                return Err(directive.error(Some("axis"), "axis should not be zero"));
+            }
+            let radius = directive.get::<f64>("radius")?;
+            if radius <= 0.0 {
+                return Err(directive.error(Some("radius"), "radius should be positive"));
+            }
+            let height = directive.get::<f64>("height")?;
+            if height <= 0.0 {
+                return Err(directive.error(Some("height"), "height should be positive"));
+            }
+            let is_capped = directive.get_optional::<bool>("capped")?.unwrap_or(true);
+
+            if kind == "cylinder" {
+                Box::new(Cylinder::new(
+                    base_center,
+                    axis,
+                    radius,
+                    height,
+                    is_capped,
+                    material,
+                ))
+            } else {
+                Box::new(Cone::new(
+                    base_center,
+                    axis,
+                    radius,
+                    height,
+                    is_capped,
+                    material,
+                ))
+            }
+        }
+        "torus" => {
+            let center = directive.get::<Vector>("center")?;
+            let axis = directive.get::<Vector>("axis")?;
+            if axis.sq_norm() == 0.0 {
+                return Err(directive.error(Some("axis"), "axis should not be zero"));
+            }
+            let major_radius = directive.get::<f64>("major_radius")?;
+            if major_radius <= 0.0 {
+                return Err(
+                    directive.error(Some("major_radius"), "major radius should be positive")
+                );
+            }
+            let minor_radius = directive.get::<f64>("minor_radius")?;
+            if minor_radius <= 0.0 {
+                return Err(
+                    directive.error(Some("minor_radius"), "minor radius should be positive")
+                );
+            }
+
+            Box::new(Torus::new(
+                center,
+                axis,
+                major_radius,
+                minor_radius,
+                material,
+            ))
+        }
+        kind => {
+            return Err(directive.error(Some("kind"), format!("unknown solid `{kind}`")));
+        }
+    };
+
+    Ok(object)
+}
+
+/// Loads the object's optional transform: scaling by `scale`, then rotations by `rotate` angles
+/// (in degrees) around x, y and z axes and then translation by `translate`
+fn load_transform(directive: &mut Directive) -> Result<Option<Mat4>, SceneLoadError> {
+    let scale = directive.get_optional::<Vector>("scale")?;
+    if scale.is_some_and(|scale| scale.x() * scale.y() * scale.z() == 0.0) {
+        return Err(directive.error(Some("scale"), "scale factors should not be zero"));
+    }
+    let rotate = directive.get_optional::<Vector>("rotate")?;
+    let translate = directive.get_optional::<Vector>("translate")?;
+
+    if scale.is_none() && rotate.is_none() && translate.is_none() {
+        return Ok(None);
+    }
+
+    let scaling = Mat4::scaling(&scale.unwrap_or(Vector::new(1.0, 1.0, 1.0)));
+    let rotation = rotate.map_or(Mat4::identity(), |angles| {
+        Mat4::rotation(&Vector::new(0.0, 0.0, 1.0), angles.z().to_radians())
+            * Mat4::rotation(&Vector::new(0.0, 1.0, 0.0), angles.y().to_radians())
+            * Mat4::rotation(&Vector::new(1.0, 0.0, 0.0), angles.x().to_radians())
+    });
+    let translation = Mat4::translation(&translate.unwrap_or_default());
+
+    Ok(Some(translation * rotation * scaling))
+}
+
+/// Accumulates the scene's parts while the directives are being loaded
+#[derive(Default)]
+struct SceneLoader {
+    /// Directory the relative file paths are resolved against
+    base_dir: PathBuf,
+    camera: Option<Camera>,
+    render_settings: Option<RenderSettings>,
+    background: Option<Box<dyn Environment>>,
+    textures: HashMap<String, Arc<dyn Texture>>,
+    materials: HashMap<String, Arc<dyn Material>>,
+    objects: Vec<Box<dyn SceneObject>>,
+    /// Named solids to be combined by constructive solid geometry
+    solids: HashMap<String, Arc<dyn SceneObject>>,
+    /// Named objects to be placed into the scene by instances
+    named_objects: HashMap<String, Vec<Arc<dyn SceneObject>>>,
+    lights: Vec<Box<dyn Light>>,
+}
+
+impl SceneLoader {
+    fn load_directive(&mut self, mut directive: Directive) -> Result<(), SceneLoadError> {
+        match directive.kind() {
+            "camera" => {
+                if self.camera.is_some() {
+                    return Err(directive.error(None, "camera is already defined"));
+                }
+                self.camera = Some(load_camera(&mut directive)?);
+            }
+            "render" => {
+                if self.render_settings.is_some() {
+                    return Err(directive.error(None, "render settings are already defined"));
+                }
+                self.render_settings = Some(load_render_settings(&mut directive)?);
+            }
+            "background" => {
+                if self.background.is_some() {
+                    return Err(directive.error(None, "background is already defined"));
+                }
+                self.background = Some(self.load_background(&mut directive)?);
+            }
+            "texture" => {
+                let name = directive.get_arg(0, "name")?;
+                if self.textures.contains_key(name) {
+                    return Err(directive
+                        .error(Some("name"), format!("texture `{name}` is already defined")));
+                }
+
+                let texture = self.load_texture(&mut directive)?;
+                self.textures.insert(name.to_string(), texture);
+            }
+            "material" => {
+                let name = directive.get_arg(0, "name")?;
+                if self.materials.contains_key(name) {
+                    return Err(directive.error(
+                        Some("name"),
+                        format!("material `{name}` is already defined"),
+                    ));
+                }
+
+                let material = self.load_material(&mut directive)?;
+                self.materials.insert(name.to_string(), material);
+            }
+            "sphere" | "box" | "cylinder" | "cone" | "torus" | "quad" | "plane" | "disk"
+            | "triangle" | "mesh" | "csg" => {
+                let objects = self.load_objects(directive.kind(), &mut directive)?;
+                let matrix = load_transform(&mut directive)?;
+
+                for object in objects {
+                    match matrix {
+                        Some(matrix) => self.objects.push(Box::new(Transformed::new(
+                            object.into(),
+                            matrix,
+                            None,
+                        ))),
+                        None => self.objects.push(object),
+                    }
+                }
+            }
+            "solid" => {
+                let name = directive.get_arg(0, "name")?;
+                if self.solids.contains_key(name) {
+                    return Err(
+                        directive.error(Some("name"), format!("solid `{name}` is already defined"))
+                    );
+                }
+
+                // operands' materials are replaced by the combination's one
+                let material: Arc<dyn Material> =
+                    Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5)));
+                let kind = directive.get_arg(1, "kind")?;
+                let solid: Arc<dyn SceneObject> = match CsgOperation::from_name(kind) {
+                    Some(operation) => {
+                        Arc::new(self.load_csg(operation, &mut directive, material)?)
+                    }
+                    None => load_solid(kind, &mut directive, material)?.into(),
+                };
+
+                // objects not enclosing a volume have no intervals along any ray
+                let probe = Ray::new(Vector::default(), Vector::new(1.0, 0.0, 0.0));
+                if solid.calc_ray_intervals(&probe).is_none() {
+                    return Err(directive.error(
+                        Some("kind"),
+                        format!("solid `{name}` should enclose a volume"),
+                    ));
+                }
+
+                self.solids.insert(name.to_string(), solid);
+            }
+            "object" => {
+                let name = directive.get_arg(0, "name")?;
+                if self.named_objects.contains_key(name) {
+                    return Err(directive
+                        .error(Some("name"), format!("object `{name}` is already defined")));
+                }
+
+                let kind = directive.get_arg(1, "kind")?;
+                let objects = self.load_objects(kind, &mut directive)?;
+                let matrix = load_transform(&mut directive)?;
+
+                let objects = objects
+                    .into_iter()
+                    .map(|object| -> Arc<dyn SceneObject> {
+                        match matrix {
+                            Some(matrix) => Arc::new(Transformed::new(object.into(), matrix, None)),
+                            None => object.into(),
+                        }
+                    })
+                    .collect();
+                self.named_objects.insert(name.to_string(), objects);
+            }
+            "instance" => {
+                let name = directive.get::<String>("object")?;
+                let objects = self.named_objects.get(&name).ok_or_else(|| {
+                    directive.error(Some("object"), format!("object `{name}` is not defined"))
+                })?;
+                let material = match directive.get_optional::<String>("material")? {
+                    Some(_) => Some(self.get_material(&mut directive)?),
+                    None => None,
+                };
+                let matrix = load_transform(&mut directive)?.unwrap_or_default();
+
+                for object in objects {
+                    self.objects.push(Box::new(Transformed::new(
+                        object.clone(),
+                        matrix,
+                        material.clone(),
+                    )));
+                }
+            }
+            "light" => {
+                let light = load_light(&mut directive)?;
+                self.lights.push(light);
+            }
+            kind => {
+                return Err(directive.error(None, format!("unknown directive `{kind}`")));
+            }
+        }
+
+        directive.finish()
+    }
+
+    /// Loads the objects of the given kind (several objects for a mesh of several materials)
+    fn load_objects(
+        &self,
+        kind: &str,
+        directive: &mut Directive,
+    ) -> Result<Vec<Box<dyn SceneObject>>, SceneLoadError> {
+        let objects: Vec<Box<dyn SceneObject>> = match kind {
+            "sphere" | "box" | "cylinder" | "cone" | "torus" => {
+                let material = self.get_material(directive)?;
+
+                vec![load_solid(kind, directive, material)?]
+            }
+            "quad" => {
+                let corner = directive.get::<Vector>("corner")?;
+                let u = directive.get::<Vector>("u")?;
+                let v = directive.get::<Vector>("v")?;
+                if u.cross(&v).sq_norm() == 0.0 {
+                    return Err(
+                        directive.error(Some("v"), "quad's edges should not be parallel or zero")
+                    );
+                }
+                let material = self.get_material(directive)?;
+
+                vec![Box::new(Quad::new(corner, u, v, material))]
+            }
+            "plane" => {
+                let point = directive.get::<Vector>("point")?;
+                let normal_vector = directive.get::<Vector>("normal")?;
+                if normal_vector.sq_norm() == 0.0 {
+                    return Err(directive.error(Some("normal"), "normal should not be zero"));
+                }
+                let material = self.get_material(directive)?;
+
+                vec![Box::new(Plane::new(point, normal_vector, material))]
+            }
+            "disk" => {
+                let center = directive.get::<Vector>("center")?;
+                let normal_vector = directive.get::<Vector>("normal")?;
+                if normal_vector.sq_norm() == 0.0 {
+                    return Err(directive.error(Some("normal"), "normal should not be zero"));
+                }
+                let radius = directive.get::<f64>("radius")?;
+                if radius <= 0.0 {
+                    return Err(directive.error(Some("radius"), "radius should be positive"));
+                }
+                let material = self.get_material(directive)?;
+
+                vec![Box::new(Disk::new(center, normal_vector, radius, material))]
+            }
+            "triangle" => {
+                let a = directive.get::<Vector>("a")?;
+                let b = directive.get::<Vector>("b")?;
+                let c = directive.get::<Vector>("c")?;
+                if (b - a).cross(&(c - a)).sq_norm() == 0.0 {
+                    return Err(directive.error(
+                        Some("c"),
+                        "triangle's vertices should not lie on the same line",
+                    ));
+                }
+                let material = self.get_material(directive)?;
+
+                vec![Box::new(Triangle::new(a, b, c, material))]
+            }
+            "csg" => {
+                let operation_name = directive.get_arg(0, "operation")?;
+                let operation = CsgOperation::from_name(operation_name).ok_or_else(|| {
+                    directive.error(
+                        Some("operation"),
+                        format!("unknown operation `{operation_name}`"),
+                    )
+                })?;
+                let material = self.get_material(directive)?;
+
+                vec![Box::new(self.load_csg(operation, directive, material)?)]
+            }
+            "mesh" => {
+                let path = self.base_dir.join(directive.get::<String>("path")?);
+                let default_material = match directive.get_optional::<String>("material")? {
+                    Some(_) => self.get_material(directive)?,
+                    None => Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+                };
+
+                let meshes = load_obj_from_file(&path, default_material).map_err(|e| {
+                    directive.error(Some("path"), format!("couldn't load mesh: {e}"))
+                })?;
+                if meshes.is_empty() {
+                    return Err(directive.error(
+                        Some("path"),
+                        format!("mesh {} has no faces", path.display()),
+                    ));
+                }
+
+                meshes
+                    .into_iter()
+                    .map(|mesh| -> Box<dyn SceneObject> { Box::new(mesh) })
+                    .collect()
+            }
+            kind => {
+                return Err(directive.error(Some("kind"), format!("unknown object `{kind}`")));
+            }
+        };
+
+        Ok(objects)
+    }
+
+    /// Loads the combination of the previously defined solids referred to by the directive's `a` and `b` fields
+    fn load_csg(
+        &self,
+        operation: CsgOperation,
+        directive: &mut Directive,
+        material: Arc<dyn Material>,
+    ) -> Result<Csg, SceneLoadError> {
+        let mut get_solid = |field: &str| -> Result<Arc<dyn SceneObject>, SceneLoadError> {
+            let name = directive.get::<String>(field)?;
+
+            self.solids.get(&name).cloned().ok_or_else(|| {
+                directive.error(Some(field), format!("solid `{name}` is not defined"))
+            })
+        };
+
+        let lhs = get_solid("a")?;
+        let rhs = get_solid("b")?;
+
+        Ok(Csg::new(operation, lhs, rhs, material))
+    }
+
+    /// Returns the previously defined material referred to by the directive's `material` field
+    fn get_material(&self, directive: &mut Directive) -> Result<Arc<dyn Material>, SceneLoadError> {
+        let name = directive.get::<String>("material")?;
+
+        self.materials.get(&name).cloned().ok_or_else(|| {
+            directive.error(
+                Some("material"),
+                format!("material `{name}` is not defined"),
+            )
+        })
+    }
+
+    fn load_material(
+        &self,
+        directive: &mut Directive,
+    ) -> Result<Arc<dyn Material>, SceneLoadError> {
+        let material = self.load_surface_material(directive)?;
+
+        let map = match (
+            self.get_optional_texture(directive, "normal_map")?,
+            self.get_optional_texture(directive, "bump_map")?,
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(directive.error(
+                    Some("bump_map"),
+                    "material should have either a normal map or a bump map",
+                ))
+            }
+            (Some(texture), None) => SurfaceMap::Normal(texture),
+            (None, Some(texture)) => SurfaceMap::Bump(texture),
+            (None, None) => return Ok(material),
+        };
+
+        let strength = directive.get_optional::<f64>("map_strength")?;
+        if strength.is_some_and(|strength| strength < 0.0) {
+            return Err(
+                directive.error(Some("map_strength"), "map strength should not be negative")
+            );
+        }
+
+        Ok(Arc::new(NormalMapped::new(material, map, strength)))
+    }
+
+    /// Loads the material of the directive's type without the normal or the bump map
+    fn load_surface_material(
+        &self,
+        directive: &mut Directive,
+    ) -> Result<Arc<dyn Material>, SceneLoadError> {
+        match directive.get_arg(1, "type")? {
+            "lambertian" => Ok(Arc::new(Lambertian::from_texture(
+                self.get_texture(directive, "albedo")?,
+            ))),
+            "metal" => {
+                let albedo = directive.get::<Vector>("albedo")?;
+                let fuzz = directive.get_optional::<f64>("fuzz")?;
+                if fuzz.is_some_and(|fuzz| !(0.0..=1.0).contains(&fuzz)) {
+                    return Err(directive.error(Some("fuzz"), "fuzz should be in [0, 1] range"));
+                }
+
+                Ok(Arc::new(Metal::new(albedo, fuzz)))
+            }
+            "dielectric" => {
+                let refraction_index = directive.get::<f64>("refraction_index")?;
+                if refraction_index <= 0.0 {
+                    return Err(directive.error(
+                        Some("refraction_index"),
+                        "refraction index should be positive",
+                    ));
+                }
+
+                Ok(Arc::new(Dielectric::new(refraction_index)))
+            }
+            "diffuse_light" => Ok(Arc::new(DiffuseLight::new(
+                directive.get::<Vector>("emit")?,
+            ))),
+            kind => Err(directive.error(
+                Some("type"),
+                format!(
+                    "unknown material type `{kind}` \
+                     (expected `lambertian`, `metal`, `dielectric` or `diffuse_light`)"
+                ),
+            )),
+        }
+    }
+
+    /// Returns the texture given by the directive's `field`:
+    /// either a color or the name of a previously defined texture
+    fn get_texture(
+        &self,
+        directive: &mut Directive,
+        field: &str,
+    ) -> Result<Arc<dyn Texture>, SceneLoadError> {
+        let value = directive.get::<String>(field)?;
+        if let Ok(color) = Vector::parse_field(&value) {
+            return Ok(Arc::new(SolidColor::new(color)));
+        }
+
+        self.textures.get(&value).cloned().ok_or_else(|| {
+            directive.error(
+                Some(field),
+                format!("expected a color or a texture name, texture `{value}` is not defined"),
+            )
+        })
+    }
+
+    /// Returns the texture given by the directive's optional `field` (see `get_texture`)
+    fn get_optional_texture(
+        &self,
+        directive: &mut Directive,
+        field: &str,
+    ) -> Result<Option<Arc<dyn Texture>>, SceneLoadError> {
+        match directive.get_optional::<String>(field)? {
+            Some(_) => Ok(Some(self.get_texture(directive, field)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn load_texture(&self, directive: &mut Directive) -> Result<Arc<dyn Texture>, SceneLoadError> {
+        let kind = directive.get_arg(1, "type")?;
+
+        // only the patterns have a scale
+        let scale = if kind == "checker" || NoisePattern::from_name(kind).is_some() {
+            let scale = directive.get_optional::<f64>("scale")?;
+            if scale.is_some_and(|scale| scale <= 0.0) {
+                return Err(directive.error(Some("scale"), "scale should be positive"));
+            }
+            scale
+        } else {
+            None
+        };
+
+        if let Some(pattern) = NoisePattern::from_name(kind) {
+            let color = directive.get_optional::<Vector>("color")?;
+
+            return Ok(Arc::new(NoiseTexture::new(pattern, scale, color)));
+        }
+
+        match kind {
+            "solid" => Ok(Arc::new(SolidColor::new(directive.get::<Vector>("color")?))),
+            "checker" => Ok(Arc::new(Checker::new(
+                self.get_texture(directive, "even")?,
+                self.get_texture(directive, "odd")?,
+                scale,
+            ))),
+            "image" => {
+                // data images (e.g. normal maps) are usually stored linear
+                let transfer_function = match directive.get_optional::<String>("transfer")? {
+                    Some(name) => TransferFunction::from_name(&name).ok_or_else(|| {
+                        directive.error(
+                            Some("transfer"),
+                            format!(
+                                "unknown transfer function `{name}` \
+                                 (expected `srgb`, `linear`, `gamma` or `gamma:<G>`)"
+                            ),
+                        )
+                    })?,
+                    None => TransferFunction::Srgb,
+                };
+
+                let path = self.base_dir.join(directive.get::<String>("path")?);
+                let image = read_framebuffer_from_file_with_transfer(&path, &transfer_function)
+                    .map_err(|e| {
+                        directive.error(
+                            Some("path"),
+                            format!("couldn't read image {}: {e}", path.display()),
+                        )
+                    })?;
+
+                let wrap_mode = match directive.get_optional::<String>("wrap")? {
+                    Some(name) => Some(WrapMode::from_name(&name).ok_or_else(|| {
+                        directive.error(
+                            Some("wrap"),
+                            format!(
+                                "unknown wrap mode `{name}` (expected `repeat`, `clamp` or `mirror`)"
+                            ),
+                        )
+                    })?),
+                    None => None,
+                };
+
+                Ok(Arc::new(ImageTexture::new(image, wrap_mode)))
+            }
+            kind => Err(directive.error(
+                Some("type"),
+                format!(
+                    "unknown texture type `{kind}` \
+                     (expected `solid`, `checker`, `image`, `noise`, `turbulence` or `marble`)"
+                ),
+            )),
+        }
+    }
+
+    fn load_background(
+        &self,
+        directive: &mut Directive,
+    ) -> Result<Box<dyn Environment>, SceneLoadError> {
+        match directive.get_arg(0, "type")? {
+            "solid" => Ok(Box::new(Solid::new(directive.get::<Vector>("color")?))),
+            "gradient" => Ok(Box::new(VerticalGradient::new(
+                directive.get::<Vector>("bottom")?,
+                directive.get::<Vector>("top")?,
+            ))),
+            "image" => {
+                let path = self.base_dir.join(directive.get::<String>("path")?);
+                let image = read_framebuffer_from_file(&path).map_err(|e| {
+                    directive.error(
+                        Some("path"),
+                        format!("couldn't read image {}: {e}", path.display()),
+                    )
+                })?;
+
+                let strength = directive.get_optional::<f64>("strength")?;
+                if strength.is_some_and(|strength| strength < 0.0) {
+                    return Err(
+                        directive.error(Some("strength"), "strength should be non-negative")
+                    );
+                }
+
+                Ok(Box::new(EquirectangularMap::new(image, strength)))
+            }
+            kind => Err(directive.error(
+                Some("type"),
+                format!(
+                    "unknown background type `{kind}` (expected `solid`, `gradient` or `image`)"
+                ),
+            )),
+        }
+    }
+
+    fn finish(self) -> SceneDescription {
+        let mut scene = Scene::new(self.camera.unwrap_or_default());
+
+        if let Some(background) = self.background {
+            scene.set_environment(background);
+        }
+        scene.add_objects(self.objects);
+        for light in self.lights {
+            scene.add_light(light);
+        }
+
+        SceneDescription {
+            scene,
+            render_settings: self.render_settings.unwrap_or_default(),
+        }
+    }
+}
+
+fn load_camera(directive: &mut Directive) -> Result<Camera, SceneLoadError> {
+    let look_from = directive.get::<Vector>("look_from")?;
+    let look_at = directive.get::<Vector>("look_at")?;
+    let vup = directive
+        .get_optional::<Vector>("vup")?
+        .unwrap_or(Vector::new(0.0, 1.0, 0.0));
+    let vfov = directive.get::<f64>("vfov")?;
+    let aspect_ratio = directive
+        .get_optional::<f64>("aspect_ratio")?
+        .unwrap_or(16.0 / 9.0);
+    let defocus_angle = directive.get_optional::<f64>("defocus_angle")?;
+    let focus_dist = directive.get_optional::<f64>("focus_dist")?;
+
+    // the checks below prevent `Camera::new` from panicking
+    let view_vector = look_from - look_at;
+    if view_vector.sq_norm() == 0.0 {
+        return Err(directive.error(Some("look_at"), "camera should not look at its own center"));
+    }
+    if vup.cross(&view_vector).sq_norm() == 0.0 {
+        return Err(directive.error(
+            Some("vup"),
+            "vup should not be parallel to the view direction",
+        ));
+    }
+    if vfov <= 0.0 || vfov >= 180.0 {
+        return Err(directive.error(Some("vfov"), "vfov should be in (0, 180) degrees range"));
+    }
+    if aspect_ratio <= 0.0 {
+        return Err(directive.error(Some("aspect_ratio"), "aspect ratio should be positive"));
+    }
+    if defocus_angle.is_some_and(|angle| !(0.0..180.0).contains(&angle)) {
+        return Err(directive.error(
+            Some("defocus_angle"),
+            "defocus angle should be in [0, 180) degrees range",
+        ));
+    }
+    if focus_dist.is_some_and(|dist| dist <= 0.0) {
+        return Err(directive.error(Some("focus_dist"), "focus distance should be positive"));
+    }
+
+    Ok(Camera::new(
+        look_from,
+        look_at,
+        vup,
+        vfov,
+        aspect_ratio,
+        defocus_angle,
+        focus_dist,
+    ))
+}
+
+fn load_render_settings(directive: &mut Directive) -> Result<RenderSettings, SceneLoadError> {
+    let img_width = directive.get_optional::<u16>("img_width")?;
+    if img_width == Some(0) {
+        return Err(directive.error(Some("img_width"), "image width should be positive"));
+    }
+
+    Ok(RenderSettings {
+        img_width,
+        antialiasing_iters: directive.get_optional::<u16>("antialiasing_iters")?,
+        reflection_max_iters: directive.get_optional::<u16>("reflection_max_iters")?,
+    })
+}
+
+fn load_light(directive: &mut Directive) -> Result<Box<dyn Light>, SceneLoadError> {
+    match directive.get_arg(0, "type")? {
+        "point" => Ok(Box::new(PointLight::new(
+            directive.get::<Vector>("position")?,
+            directive.get::<Vector>("intensity")?,
+        ))),
+        "sphere" => {
+            let center = directive.get::<Vector>("center")?;
+            let radius = directive.get::<f64>("radius")?;
+            if radius <= 0.0 {
+                return Err(directive.error(Some("radius"), "radius should be positive"));
+            }
+
+            Ok(Box::new(SphereLight::new(
+                center,
+                radius,
+                directive.get::<Vector>("emit")?,
+            )))
+        }
+        "quad" => {
+            let corner = directive.get::<Vector>("corner")?;
+            let u = directive.get::<Vector>("u")?;
+            let v = directive.get::<Vector>("v")?;
+            if u.cross(&v).sq_norm() == 0.0 {
+                return Err(
+                    directive.error(Some("v"), "quad's edges should not be parallel or zero")
+                );
+            }
+
+            Ok(Box::new(QuadLight::new(
+                corner,
+                u,
+                v,
+                directive.get::<Vector>("emit")?,
+            )))
+        }
+        kind => Err(directive.error(
+            Some("type"),
+            format!("unknown light type `{kind}` (expected `point`, `sphere` or `quad`)"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the line and the field of the syntax error the scene fails to parse with
+    fn parse_error(source: &str) -> (usize, Option<String>) {
+        match parse_scene(source) {
+            Err(SceneLoadError::Syntax { line, field, .. }) => (line, field),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("the scene should not parse"),
+        }
+    }
+
+    #[test]
+    fn metal_fuzz_out_of_range_is_rejected() {
+        for fuzz in ["-0.1", "1.5"] {
+            let source = format!("material m metal albedo=1,1,1 fuzz={fuzz}\n");
+
+            assert_eq!(parse_error(&source), (1, Some("fuzz".to_string())));
+        }
+
+        assert!(parse_scene("material m metal albedo=1,1,1 fuzz=1\n").is_ok());
+    }
+
+    #[test]
+    fn inverted_box_is_rejected() {
+        let source = "material m lambertian albedo=1,1,1\n\
+                      box min=0,0,0 max=1,-1,1 material=m\n";
+
+        assert_eq!(parse_error(source), (2, Some("max".to_string())));
+        assert!(parse_scene(
+            "material m lambertian albedo=1,1,1\nbox min=0,0,0 max=1,1,1 material=m\n"
+        )
+        .is_ok());
+    }
+}
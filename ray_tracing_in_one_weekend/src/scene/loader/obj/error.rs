@@ -0,0 +1,48 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+/// Error occurred while loading a Wavefront OBJ model or its material library
+#[derive(Debug)]
+pub enum ObjLoadError {
+    /// The file at `path` cannot be read
+    Io { path: PathBuf, error: io::Error },
+    /// Invalid statement at the given line (starting from 1) of the file at `path`
+    Syntax {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+}
+
+impl ObjLoadError {
+    pub fn syntax(path: impl Into<PathBuf>, line: usize, message: impl Into<String>) -> Self {
+        ObjLoadError::Syntax {
+            path: path.into(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ObjLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjLoadError::Io { path, error } => {
+                write!(f, "couldn't read {}: {error}", path.display())
+            }
+            ObjLoadError::Syntax {
+                path,
+                line,
+                message,
+            } => write!(f, "{}, line {line}: {message}", path.display()),
+        }
+    }
+}
+
+impl Error for ObjLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObjLoadError::Io { error, .. } => Some(error),
+            ObjLoadError::Syntax { .. } => None,
+        }
+    }
+}
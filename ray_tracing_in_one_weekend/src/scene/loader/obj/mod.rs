@@ -0,0 +1,201 @@
+//! Loader of Wavefront OBJ models.
+//!
+//! Supported statements are vertex positions `v`, surface coordinates `vt`, normals `vn`,
+//! polygonal faces `f` (split into triangles), material libraries `mtllib`
+//! and material groups `usemtl`, the other statements are ignored.
+//! Every material group becomes a separate mesh sharing the model's vertex buffers,
+//! the faces without a material (or with a material missing from the libraries)
+//! get the default one
+
+pub mod error;
+pub mod mtl;
+
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+use crate::{
+    scene::{
+        materials::Material,
+        scene_objects::triangle_mesh::{MeshBuffers, MeshFace, TriangleMesh},
+    },
+    vector::Vector,
+};
+
+use self::{error::ObjLoadError, mtl::parse_mtl};
+
+/// Loads the model from the OBJ file at `path` into meshes (one per material group)
+/// resolving the material libraries' paths against the model's directory
+pub fn load_obj_from_file(
+    path: &Path,
+    default_material: Arc<dyn Material>,
+) -> Result<Vec<TriangleMesh>, ObjLoadError> {
+    let source = read_file(path)?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut buffers = MeshBuffers::default();
+    // faces of each material group (`None` for the faces without a material)
+    let mut groups: Vec<(Option<String>, Vec<MeshFace>)> = vec![(None, vec![])];
+    let mut current_group = 0;
+    let mut materials = HashMap::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let error = |message: &str| ObjLoadError::syntax(path, i + 1, message);
+
+        let mut tokens = line
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args = tokens.collect::<Vec<_>>();
+
+        match keyword {
+            "v" => match parse_numbers(&args).as_deref() {
+                // the optional weight is ignored
+                Some([x, y, z]) | Some([x, y, z, _]) => {
+                    buffers.positions.push(Vector::new(*x, *y, *z))
+                }
+                _ => return Err(error("expected vertex position `x y z`")),
+            },
+            "vt" => match parse_numbers(&args).as_deref() {
+                Some([u]) => buffers.uvs.push((*u, 0.0)),
+                Some([u, v]) | Some([u, v, _]) => buffers.uvs.push((*u, *v)),
+                _ => return Err(error("expected surface coordinates `u [v]`")),
+            },
+            "vn" => match parse_numbers(&args).as_deref() {
+                Some([x, y, z]) => {
+                    let normal_vector = Vector::new(*x, *y, *z);
+                    if normal_vector.sq_norm() == 0.0 {
+                        return Err(error("normal should not be zero"));
+                    }
+                    buffers.normals.push(normal_vector.normalize());
+                }
+                _ => return Err(error("expected normal `x y z`")),
+            },
+            "f" => {
+                let vertices = args
+                    .iter()
+                    .map(|vertex| parse_face_vertex(vertex, &buffers))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|message| error(&message))?;
+                if vertices.len() < 3 {
+                    return Err(error("face should have at least 3 vertices"));
+                }
+
+                // the attribute is used only if every vertex of the face has it
+                let has_uvs = vertices.iter().all(|(_, uv, _)| uv.is_some());
+                let has_normals = vertices.iter().all(|(_, _, normal)| normal.is_some());
+
+                // polygons are split into a fan of triangles around the first vertex
+                for k in 1..vertices.len() - 1 {
+                    let triangle = [vertices[0], vertices[k], vertices[k + 1]];
+
+                    groups[current_group].1.push(MeshFace {
+                        positions: triangle.map(|(position, _, _)| position),
+                        normals: has_normals.then(|| triangle.map(|(_, _, n)| n.unwrap())),
+                        uvs: has_uvs.then(|| triangle.map(|(_, uv, _)| uv.unwrap())),
+                    });
+                }
+            }
+            "usemtl" => {
+                let name = args.join(" ");
+                current_group = match groups
+                    .iter()
+                    .position(|(group_name, _)| group_name.as_deref() == Some(name.as_str()))
+                {
+                    Some(index) => index,
+                    None => {
+                        groups.push((Some(name), vec![]));
+                        groups.len() - 1
+                    }
+                };
+            }
+            "mtllib" => {
+                if args.is_empty() {
+                    return Err(error("expected material library path"));
+                }
+
+                for library in args {
+                    let library_path = base_dir.join(library);
+                    let library_source = read_file(&library_path)?;
+                    materials.extend(parse_mtl(&library_source, &library_path)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let buffers = Arc::new(buffers);
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, faces)| !faces.is_empty())
+        .map(|(name, faces)| {
+            let material = name
+                .and_then(|name| materials.get(&name).cloned())
+                .unwrap_or_else(|| default_material.clone());
+
+            TriangleMesh::new(buffers.clone(), faces, material)
+        })
+        .collect())
+}
+
+fn read_file(path: &Path) -> Result<String, ObjLoadError> {
+    fs::read_to_string(path).map_err(|error| ObjLoadError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Parses the statement's arguments as numbers
+fn parse_numbers(args: &[&str]) -> Option<Vec<f64>> {
+    args.iter().map(|arg| arg.parse::<f64>().ok()).collect()
+}
+
+/// Parses the face's vertex `position[/uv][/normal]` into the indices (starting from 0)
+/// of the attributes in `buffers`
+///
+/// OBJ indices start from 1, the negative ones count back from the last defined attribute
+fn parse_face_vertex(
+    vertex: &str,
+    buffers: &MeshBuffers,
+) -> Result<(usize, Option<usize>, Option<usize>), String> {
+    let parse_index = |index: &str, len: usize, name: &str| -> Result<usize, String> {
+        let index = index
+            .parse::<i64>()
+            .map_err(|_| format!("invalid {name} index `{index}`"))?;
+
+        let resolved = match index {
+            1.. => index - 1,
+            ..=-1 => len as i64 + index,
+            0 => -1,
+        };
+
+        usize::try_from(resolved)
+            .ok()
+            .filter(|resolved| *resolved < len)
+            .ok_or_else(|| format!("{name} index {index} is out of range"))
+    };
+
+    let mut indices = vertex.split('/');
+    let position = parse_index(
+        indices.next().unwrap_or_default(),
+        buffers.positions.len(),
+        "vertex",
+    )?;
+    let uv = match indices.next() {
+        Some("") | None => None,
+        Some(index) => Some(parse_index(
+            index,
+            buffers.uvs.len(),
+            "surface coordinates",
+        )?),
+    };
+    let normal = match indices.next() {
+        Some("") | None => None,
+        Some(index) => Some(parse_index(index, buffers.normals.len(), "normal")?),
+    };
+
+    Ok((position, uv, normal))
+}
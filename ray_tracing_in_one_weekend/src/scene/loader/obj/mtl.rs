@@ -0,0 +1,138 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use crate::{
+    scene::materials::{
+        dielectric::Dielectric, diffuse_light::DiffuseLight, lambertian::Lambertian, metal::Metal,
+        Material,
+    },
+    vector::Vector,
+};
+
+use super::{error::ObjLoadError, parse_numbers};
+
+/// Material statements of a single `newmtl` entry
+struct MtlEntry {
+    name: String,
+    /// Diffuse color `Kd`
+    diffuse: Vector,
+    /// Specular color `Ks`
+    specular: Vector,
+    /// Emitted color `Ke`
+    emission: Vector,
+    /// Specular exponent `Ns` (0-1000 range)
+    shininess: f64,
+    /// Refraction index `Ni`
+    refraction_index: f64,
+    /// Opacity `d` (or 1 - `Tr`)
+    opacity: f64,
+    /// Illumination model `illum`
+    illumination: u32,
+}
+
+impl MtlEntry {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            diffuse: Vector::new(0.8, 0.8, 0.8),
+            specular: Vector::default(),
+            emission: Vector::default(),
+            shininess: 0.0,
+            refraction_index: 1.0,
+            opacity: 1.0,
+            illumination: 1,
+        }
+    }
+
+    /// Converts the entry into the closest renderer's material:
+    /// emissive entries become lights, transparent ones (`d` < 1 or `illum` 4, 6, 7) become dielectrics,
+    /// reflective ones (`illum` 3, 5) become metals with the fuzz decreasing with `Ns`,
+    /// others are lambertian
+    fn to_material(&self) -> Arc<dyn Material> {
+        if self.emission.sq_norm() > 0.0 {
+            Arc::new(DiffuseLight::new(self.emission))
+        } else if self.opacity < 1.0 || matches!(self.illumination, 4 | 6 | 7) {
+            Arc::new(Dielectric::new(self.refraction_index.max(1.0)))
+        } else if matches!(self.illumination, 3 | 5) {
+            Arc::new(Metal::new(
+                self.specular,
+                Some(1.0 - (self.shininess / 1000.0).clamp(0.0, 1.0)),
+            ))
+        } else {
+            Arc::new(Lambertian::new(self.diffuse))
+        }
+    }
+}
+
+/// Parses the material library (MTL) statements `newmtl`, `Kd`, `Ks`, `Ke`, `Ns`, `Ni`, `d`, `Tr`
+/// and `illum` (the other statements are ignored) into the materials by their names
+pub fn parse_mtl(
+    source: &str,
+    path: &Path,
+) -> Result<HashMap<String, Arc<dyn Material>>, ObjLoadError> {
+    let mut entries: Vec<MtlEntry> = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let error = |message: &str| ObjLoadError::syntax(path, i + 1, message);
+
+        let mut tokens = line
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args = tokens.collect::<Vec<_>>();
+
+        if keyword == "newmtl" {
+            let name = args.join(" ");
+            if name.is_empty() {
+                return Err(error("expected material name"));
+            }
+            entries.push(MtlEntry::new(name));
+            continue;
+        }
+
+        let is_known = matches!(
+            keyword,
+            "Kd" | "Ks" | "Ke" | "Ns" | "Ni" | "d" | "Tr" | "illum"
+        );
+        if !is_known {
+            continue;
+        }
+
+        let entry = entries
+            .last_mut()
+            .ok_or_else(|| error("expected `newmtl` before material statements"))?;
+
+        let get_color = || -> Result<Vector, ObjLoadError> {
+            match parse_numbers(&args).as_deref() {
+                Some([r, g, b]) => Ok(Vector::new(*r, *g, *b)),
+                Some([x]) => Ok(Vector::new(*x, *x, *x)),
+                _ => Err(error("expected color `r g b`")),
+            }
+        };
+        let get_number = || -> Result<f64, ObjLoadError> {
+            match parse_numbers(&args).as_deref() {
+                Some([x]) => Ok(*x),
+                _ => Err(error("expected a number")),
+            }
+        };
+
+        match keyword {
+            "Kd" => entry.diffuse = get_color()?,
+            "Ks" => entry.specular = get_color()?,
+            "Ke" => entry.emission = get_color()?,
+            "Ns" => entry.shininess = get_number()?,
+            "Ni" => entry.refraction_index = get_number()?,
+            "d" => entry.opacity = get_number()?,
+            "Tr" => entry.opacity = 1.0 - get_number()?,
+            _ => entry.illumination = get_number()? as u32,
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| (entry.name.clone(), entry.to_material()))
+        .collect())
+}
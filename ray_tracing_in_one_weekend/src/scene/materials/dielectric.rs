@@ -0,0 +1,63 @@
+use rand::{Rng, RngCore};
+
+use crate::{
+    scene::scene_objects::HitRecord,
+    vector::{Ray, Vector},
+};
+
+use super::Material;
+
+/// Transparent material (glass, water, etc.) that either reflects or refracts a ray
+pub struct Dielectric {
+    /// Refraction index of the material relative to the enclosing media (air)
+    refraction_index: f64,
+}
+
+impl Dielectric {
+    pub fn new(refraction_index: f64) -> Self
+    where
+        Self: Sized,
+    {
+        Self { refraction_index }
+    }
+
+    /// Schlick's approximation for reflectance
+    fn reflectance(cosine: f64, eta_ratio: f64) -> f64 {
+        let r0 = ((1.0 - eta_ratio) / (1.0 + eta_ratio)).powi(2);
+
+        r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+    }
+}
+
+impl Material for Dielectric {
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Vector, Ray)> {
+        let direction = ray.direction();
+
+        let normal_vector = hit.face_shading_normal();
+
+        // the ray either enters the object (front face) or leaves it
+        let eta_ratio = if hit.is_front_face {
+            1.0 / self.refraction_index
+        } else {
+            self.refraction_index
+        };
+
+        let cos_theta = (-direction.dot(&normal_vector)).min(1.0);
+        let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
+
+        let is_total_internal_reflection = eta_ratio * sin_theta > 1.0;
+
+        let scattered_direction = if is_total_internal_reflection
+            || Self::reflectance(cos_theta, eta_ratio) > rng.gen_range(0.0..1.0)
+        {
+            direction.reflect(&normal_vector)
+        } else {
+            direction.refract(&normal_vector, eta_ratio)
+        };
+
+        Some((
+            Vector::new(1.0, 1.0, 1.0),
+            Ray::new(hit.coordinates, scattered_direction),
+        ))
+    }
+}
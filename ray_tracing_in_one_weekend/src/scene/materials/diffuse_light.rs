@@ -0,0 +1,43 @@
+use rand::RngCore;
+
+use crate::{
+    scene::scene_objects::HitRecord,
+    vector::{Ray, Vector},
+};
+
+use super::Material;
+
+/// Light emitting material that absorbs every incoming ray
+pub struct DiffuseLight {
+    /// Emitted color (radiance) that may exceed 1.0 for bright lights
+    emit: Vector,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self { emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(
+        &self,
+        _ray: &Ray,
+        _hit: &HitRecord,
+        _rng: &mut dyn RngCore,
+    ) -> Option<(Vector, Ray)> {
+        None
+    }
+
+    /// Emits light from the outer side of the surface only
+    fn emitted(&self, _ray: &Ray, hit: &HitRecord) -> Vector {
+        if hit.is_front_face {
+            self.emit
+        } else {
+            Vector::default()
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::{f64::consts::PI, sync::Arc};
+
+use rand::RngCore;
+
+use crate::{
+    scene::{
+        scene_objects::HitRecord,
+        textures::{solid_color::SolidColor, Texture},
+    },
+    vector::{
+        sampling::{cosine_hemisphere_pdf, sample_cosine_hemisphere},
+        Ray, Vector,
+    },
+};
+
+use super::Material;
+
+/// Ideal diffuse (matte) material
+pub struct Lambertian {
+    /// Fraction of light reflected for each color component (0.0-1.0 range)
+    /// varying over the surface
+    albedo: Arc<dyn Texture>,
+}
+
+impl Lambertian {
+    /// Creates the material of the uniform `albedo`
+    pub fn new(albedo: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_texture(Arc::new(SolidColor::new(albedo)))
+    }
+
+    /// Creates the material with the albedo given by the `texture`
+    pub fn from_texture(texture: Arc<dyn Texture>) -> Self
+    where
+        Self: Sized,
+    {
+        Self { albedo: texture }
+    }
+
+    fn get_albedo(&self, hit: &HitRecord) -> Vector {
+        self.albedo.get_color(hit.u, hit.v, &hit.coordinates)
+    }
+}
+
+impl Material for Lambertian {
+    fn scatter(&self, _ray: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Vector, Ray)> {
+        // scatter into the hemisphere the ray came from:
+        // the cosine-weighted directions cancel out the cosine term so the attenuation is just the albedo
+        let scattered_direction = sample_cosine_hemisphere(&hit.face_shading_normal(), rng);
+
+        // the hemisphere around a perturbed normal dips beneath the surface, such rays are absorbed
+        if scattered_direction.dot(&hit.face_normal()) <= 0.0 {
+            return None;
+        }
+
+        Some((
+            self.get_albedo(hit),
+            Ray::new(hit.coordinates, scattered_direction),
+        ))
+    }
+
+    fn eval(&self, _ray: &Ray, hit: &HitRecord, direction: &Vector) -> Vector {
+        // no light arrives from beneath the surface whatever the shading normal is
+        if direction.dot(&hit.face_normal()) <= 0.0 {
+            return Vector::default();
+        }
+
+        let cos_theta = hit.face_shading_normal().dot(direction).max(0.0);
+
+        self.get_albedo(hit) * (cos_theta / PI)
+    }
+
+    fn pdf(&self, _ray: &Ray, hit: &HitRecord, direction: &Vector) -> f64 {
+        cosine_hemisphere_pdf(&hit.face_shading_normal(), direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn perturbed_normal_does_not_leak_light_through_surface() {
+        // the ray hits the plane z = 0 from above, the shading normal leans towards +x
+        let ray = Ray::new(Vector::new(0.0, 0.0, 1.0), Vector::new(0.0, 0.0, -1.0));
+        let hit = HitRecord::new(&ray, 1.0, Vector::new(0.0, 0.0, 1.0), (0.0, 0.0))
+            .with_shading_normal(&Vector::new(1.0, 0.0, 1.0).normalize());
+        let material = Lambertian::new(Vector::new(0.5, 0.5, 0.5));
+
+        // beneath the surface but above the shading normal's horizon
+        let below = Vector::new(1.0, 0.0, -0.5).normalize();
+        assert!(hit.shading_normal.dot(&below) > 0.0);
+        assert_eq!(material.eval(&ray, &hit, &below).sq_norm(), 0.0);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            if let Some((_, scattered)) = material.scatter(&ray, &hit, &mut rng) {
+                assert!(scattered.direction().z() > 0.0);
+            }
+        }
+    }
+}
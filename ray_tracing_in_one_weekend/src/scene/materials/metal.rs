@@ -0,0 +1,44 @@
+use rand::RngCore;
+
+use crate::{
+    scene::scene_objects::HitRecord,
+    vector::{Ray, Vector},
+};
+
+use super::Material;
+
+/// Reflective material with optional fuzziness of the reflection
+pub struct Metal {
+    /// Fraction of light reflected for each color component (0.0-1.0 range)
+    albedo: Vector,
+    /// Radius of the sphere the reflected ray's direction is randomly perturbed within
+    /// (0.0 stands for a perfect mirror, clamped to 1.0)
+    fuzz: f64,
+}
+
+impl Metal {
+    pub fn new(albedo: Vector, fuzz: Option<f64>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            albedo,
+            fuzz: fuzz.unwrap_or(0.0).clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Material for Metal {
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Vector, Ray)> {
+        let reflected = ray.direction().reflect(&hit.face_shading_normal());
+        let scattered_direction =
+            reflected.normalize() + Vector::gen_rand_inside_unit_sphere(rng) * self.fuzz;
+
+        // fuzzed (or reflected about a perturbed normal) rays that go beneath the surface are absorbed
+        if scattered_direction.dot(&hit.face_normal()) <= 0.0 {
+            return None;
+        }
+
+        Some((self.albedo, Ray::new(hit.coordinates, scattered_direction)))
+    }
+}
@@ -0,0 +1,45 @@
+pub mod dielectric;
+pub mod diffuse_light;
+pub mod lambertian;
+pub mod metal;
+pub mod normal_mapped;
+
+use rand::RngCore;
+
+use crate::vector::{Ray, Vector};
+
+use super::scene_objects::HitRecord;
+
+/// Describes how an object's surface interacts with an incoming ray
+pub trait Material: Send + Sync {
+    /// Scatters the incoming `ray` that hits the surface as described by `hit`.
+    ///
+    /// Random decisions are made with the given `rng`.
+    ///
+    /// Returns the attenuation (0.0-1.0 range for each color component) and the scattered ray
+    /// or `None` if the ray is absorbed
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Vector, Ray)>;
+
+    /// Returns the color (radiance) the surface emits towards the origin of the `ray`
+    /// that hits it as described by `hit` (non-emissive surfaces emit nothing)
+    fn emitted(&self, _ray: &Ray, _hit: &HitRecord) -> Vector {
+        Vector::default()
+    }
+
+    /// Returns the fraction of light arriving from `direction` (unit length)
+    /// that the surface scatters along the incoming `ray` back to its origin,
+    /// i.e. the scattering function multiplied by the cosine of the angle with the normal.
+    ///
+    /// Specular materials (the ones that scatter into a single direction) return black
+    fn eval(&self, _ray: &Ray, _hit: &HitRecord, _direction: &Vector) -> Vector {
+        Vector::default()
+    }
+
+    /// Returns the probability density (per unit solid angle)
+    /// of `scatter` choosing `direction` (unit length)
+    ///
+    /// Specular materials return 0.0, such materials are not lit by light sampling
+    fn pdf(&self, _ray: &Ray, _hit: &HitRecord, _direction: &Vector) -> f64 {
+        0.0
+    }
+}
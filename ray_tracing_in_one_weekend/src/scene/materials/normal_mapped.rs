@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::{
+    scene::{scene_objects::HitRecord, textures::Texture},
+    vector::{Ray, Vector},
+};
+
+use super::Material;
+
+/// Step of the surface coordinates (and of the coordinates along the tangents)
+/// the height's slope is computed over
+const BUMP_DELTA: f64 = 1e-3;
+
+/// Texture perturbing the shading normal of a surface
+pub enum SurfaceMap {
+    /// Tangent-space normal map: the color components (0.0-1.0 range) encode
+    /// the normal's coordinates (-1.0-1.0 range) along the tangent, the bitangent and the normal
+    Normal(Arc<dyn Texture>),
+    /// Height map: the average of the color components is the height above the surface
+    ///
+    /// The slopes are taken per unit of the surface coordinates for the textures
+    /// varying with (`u`, `v`), so the detail does not depend on the object's size,
+    /// and per unit of length for the textures varying over the space (noise, checker)
+    Bump(Arc<dyn Texture>),
+}
+
+/// Material whose surface detail is given by a normal or a bump map
+/// without changing the geometry of the objects
+///
+/// The map perturbs the shading normal the wrapped material scatters the light around
+pub struct NormalMapped {
+    material: Arc<dyn Material>,
+    map: SurfaceMap,
+    /// Scale of the perturbation (0.0 leaves the surface smooth),
+    /// the heights of bump maps are multiplied by it
+    strength: f64,
+}
+
+impl NormalMapped {
+    pub fn new(material: Arc<dyn Material>, map: SurfaceMap, strength: Option<f64>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            map,
+            strength: strength.unwrap_or(1.0),
+        }
+    }
+
+    /// Returns the hit record with the shading normal perturbed by the map
+    fn perturb(&self, hit: &HitRecord) -> HitRecord {
+        let (normal, tangent, bitangent) = (hit.shading_normal, hit.tangent, hit.bitangent());
+
+        let perturbed = match &self.map {
+            SurfaceMap::Normal(texture) => {
+                let local = texture.get_color(hit.u, hit.v, &hit.coordinates) * 2.0
+                    - Vector::new(1.0, 1.0, 1.0);
+
+                tangent * (local.x() * self.strength)
+                    + bitangent * (local.y() * self.strength)
+                    + normal * local.z()
+            }
+            SurfaceMap::Bump(texture) => {
+                // the height's slopes along the tangents tilt the normal away from the rise,
+                // the step is the same in the surface coordinates and along the tangents
+                // (see `SurfaceMap::Bump`)
+                let height = |du: f64, dv: f64| {
+                    let point = hit.coordinates + tangent * du + bitangent * dv;
+                    let color = texture.get_color(hit.u + du, hit.v + dv, &point);
+
+                    (color.x() + color.y() + color.z()) / 3.0
+                };
+                let height_at_hit = height(0.0, 0.0);
+                let slope_u = (height(BUMP_DELTA, 0.0) - height_at_hit) / BUMP_DELTA;
+                let slope_v = (height(0.0, BUMP_DELTA) - height_at_hit) / BUMP_DELTA;
+
+                normal - (tangent * slope_u + bitangent * slope_v) * self.strength
+            }
+        };
+
+        // normals turned away from the surface (by a broken map) are ignored
+        if perturbed.dot(&normal) > 0.0 {
+            hit.with_shading_normal(&perturbed.normalize())
+        } else {
+            *hit
+        }
+    }
+}
+
+impl Material for NormalMapped {
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Vector, Ray)> {
+        self.material.scatter(ray, &self.perturb(hit), rng)
+    }
+
+    fn emitted(&self, ray: &Ray, hit: &HitRecord) -> Vector {
+        self.material.emitted(ray, &self.perturb(hit))
+    }
+
+    fn eval(&self, ray: &Ray, hit: &HitRecord, direction: &Vector) -> Vector {
+        self.material.eval(ray, &self.perturb(hit), direction)
+    }
+
+    fn pdf(&self, ray: &Ray, hit: &HitRecord, direction: &Vector) -> f64 {
+        self.material.pdf(ray, &self.perturb(hit), direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scene::{
+        materials::lambertian::Lambertian,
+        scene_objects::{quad::Quad, RayToObjectHandler},
+    };
+
+    use super::*;
+
+    /// Height rising along `u`
+    struct Ramp;
+
+    impl Texture for Ramp {
+        fn get_color(&self, u: f64, _v: f64, _point: &Vector) -> Vector {
+            Vector::new(u, u, u)
+        }
+    }
+
+    #[test]
+    fn bump_strength_is_in_surface_coordinates() {
+        let material = NormalMapped::new(
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+            SurfaceMap::Bump(Arc::new(Ramp)),
+            Some(0.5),
+        );
+
+        let normals = [1.0, 10.0].map(|size| {
+            let quad = Quad::new(
+                Vector::default(),
+                Vector::new(size, 0.0, 0.0),
+                Vector::new(0.0, size, 0.0),
+                Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+            );
+            let ray = Ray::new(
+                Vector::new(size / 2.0, size / 2.0, 1.0),
+                Vector::new(0.0, 0.0, -1.0),
+            );
+            let hit = quad
+                .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+                .unwrap();
+
+            material.perturb(&hit).shading_normal
+        });
+
+        // the slope along `u` is 1.0 per unit, tilting the normal against +x
+        let expected = Vector::new(-0.5, 0.0, 1.0).normalize();
+        for normal in normals {
+            assert!((normal - expected).sq_norm() < 1e-12, "{normal:?}");
+        }
+    }
+}
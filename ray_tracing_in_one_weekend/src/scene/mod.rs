@@ -1,12 +1,24 @@
+pub mod bvh;
 pub mod camera;
+pub mod environments;
+pub mod framebuffer;
+pub mod lights;
+pub mod loader;
+pub mod materials;
+pub mod output_transform;
 pub mod render;
 pub mod scene_objects;
-mod utils;
+pub mod textures;
 
+use std::ops::Range;
+
+use bvh::Bvh;
 use camera::Camera;
-use scene_objects::SceneObject;
+use environments::{vertical_gradient::VerticalGradient, Environment};
+use lights::Light;
+use scene_objects::{HitRecord, SceneObject};
 
-use crate::vector::Vector;
+use crate::vector::{Ray, Vector};
 
 use self::render::Renderer;
 
@@ -27,11 +39,18 @@ impl From<Vector> for Color {
 /// Describes the whole scene:
 /// - camera & viewport
 /// - scene objects
+/// - lights
 /// - render params
-/// - background
+/// - environment
 pub struct Scene {
     camera: Camera,
     objects: Vec<Box<dyn SceneObject>>,
+    /// Lights sampled directly (their emitting surfaces are among `objects`)
+    lights: Vec<Box<dyn Light>>,
+    /// Bounding volume hierarchy over `objects` (built before rendering)
+    bvh: Option<Bvh>,
+    /// Light coming from the directions where no object is hit
+    environment: Box<dyn Environment>,
     renderer: Renderer,
 }
 
@@ -43,15 +62,86 @@ impl Scene {
         Self {
             camera,
             objects,
+            lights: vec![],
+            bvh: None,
+            environment: Box::new(VerticalGradient::default()),
             renderer,
         }
     }
 
+    pub fn aspect_ratio(&self) -> f64 {
+        self.camera.viewport.aspect_ratio
+    }
+
+    /// Changes the camera's aspect ratio (and hence the rendered image's height)
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f64) {
+        self.camera.set_aspect_ratio(aspect_ratio);
+        self.renderer = Renderer::empty();
+    }
+
+    /// Replaces the environment (the white to light blue sky gradient by default)
+    pub fn set_environment(&mut self, environment: Box<dyn Environment>) {
+        self.environment = environment;
+    }
+
     pub fn add_object(&mut self, object: Box<dyn SceneObject>) {
         self.objects.push(object);
+        self.bvh = None;
     }
 
     pub fn add_objects(&mut self, objects: Vec<Box<dyn SceneObject>>) {
         self.objects.extend(objects);
+        self.bvh = None;
+    }
+
+    /// Adds the light along with its emitting surface (if it has one)
+    pub fn add_light(&mut self, light: Box<dyn Light>) {
+        if let Some(object) = light.get_object() {
+            self.add_object(object);
+        }
+        self.lights.push(light);
+    }
+
+    /// Builds the bounding volume hierarchy over the scene objects if it is not built yet
+    fn build_bvh(&mut self) {
+        if self.bvh.is_none() {
+            let bounding_boxes = self
+                .objects
+                .iter()
+                .map(|object| object.get_bounding_box())
+                .collect::<Vec<_>>();
+
+            self.bvh = Some(Bvh::new(&bounding_boxes));
+        }
+    }
+
+    /// Returns the closest (by the ray parameter `t`) intersection between the given ray
+    /// and the scene objects within `t_range` along with the object that is hit.
+    ///
+    /// Traverses the bounding volume hierarchy if it is built
+    /// or tests every object otherwise
+    fn calc_closest_intersection(
+        &self,
+        ray: &Ray,
+        t_range: Range<f64>,
+    ) -> Option<(&dyn SceneObject, HitRecord)> {
+        if let Some(bvh) = &self.bvh {
+            return bvh
+                .calc_closest_intersection(ray, t_range, |i, t_range| {
+                    self.objects[i].calc_ray_intersection(ray, t_range)
+                })
+                .map(|(i, hit)| (self.objects[i].as_ref(), hit));
+        }
+
+        self.objects.iter().fold(None, |closest, object| {
+            let t_max = closest
+                .as_ref()
+                .map_or(t_range.end, |(_, hit): &(_, HitRecord)| hit.t);
+
+            object
+                .calc_ray_intersection(ray, t_range.start..t_max)
+                .map(|hit| (object.as_ref(), hit))
+                .or(closest)
+        })
     }
 }
@@ -0,0 +1,155 @@
+use crate::vector::Vector;
+
+use super::Color;
+
+/// Operator compressing high dynamic range colors into 0.0-1.0 range
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ToneMapping {
+    /// Colors are clamped so highlights clip hard
+    #[default]
+    Clamp,
+    /// Reinhard operator `x / (1 + x)` applied to each color component
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve applied to each color component
+    AcesFilmic,
+}
+
+impl ToneMapping {
+    pub const ALL: [ToneMapping; 3] = [
+        ToneMapping::Clamp,
+        ToneMapping::Reinhard,
+        ToneMapping::AcesFilmic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ToneMapping::Clamp => "clamp",
+            ToneMapping::Reinhard => "reinhard",
+            ToneMapping::AcesFilmic => "aces",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|tone_mapping| tone_mapping.name().eq_ignore_ascii_case(name))
+    }
+
+    fn map(&self, x: f64) -> f64 {
+        match self {
+            ToneMapping::Clamp => x,
+            ToneMapping::Reinhard => x / (1.0 + x),
+            ToneMapping::AcesFilmic => (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14),
+        }
+        .clamp(0.0, 1.0)
+    }
+}
+
+/// Function encoding linear colors (0.0-1.0 range) for display
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferFunction {
+    /// Colors are stored as is
+    Linear,
+    /// Power law encoding `x^(1 / gamma)`
+    Gamma(f64),
+    /// sRGB opto-electronic transfer function
+    Srgb,
+}
+
+impl TransferFunction {
+    /// Gamma of the power law encoding used by most displays
+    pub const DEFAULT_GAMMA: f64 = 2.2;
+
+    /// Parses `linear`, `srgb`, `gamma` (with the default gamma) or `gamma:<G>`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "linear" => Some(TransferFunction::Linear),
+            "srgb" => Some(TransferFunction::Srgb),
+            "gamma" => Some(TransferFunction::Gamma(Self::DEFAULT_GAMMA)),
+            name => name
+                .strip_prefix("gamma:")
+                .and_then(|gamma| gamma.parse::<f64>().ok())
+                .filter(|gamma| gamma.is_finite() && *gamma > 0.0)
+                .map(TransferFunction::Gamma),
+        }
+    }
+
+    fn encode(&self, x: f64) -> f64 {
+        match self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(1.0 / gamma),
+            TransferFunction::Srgb if x <= 0.0031308 => 12.92 * x,
+            TransferFunction::Srgb => 1.055 * x.powf(1.0 / 2.4) - 0.055,
+        }
+    }
+
+    /// Decodes the encoded color component (0.0-1.0 range) back into the linear one
+    pub fn decode(&self, x: f64) -> f64 {
+        match self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(*gamma),
+            TransferFunction::Srgb if x <= 0.04045 => x / 12.92,
+            TransferFunction::Srgb => ((x + 0.055) / 1.055).powf(2.4),
+        }
+    }
+}
+
+/// Converts the renderer's linear high dynamic range colors into displayable 8-bit colors:
+/// the color is scaled by the exposure, tone mapped into 0.0-1.0 range
+/// and encoded with the transfer function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutputTransform {
+    /// Exposure adjustment in stops (the color is multiplied by `2^exposure`)
+    pub exposure: f64,
+    pub tone_mapping: ToneMapping,
+    pub transfer_function: TransferFunction,
+}
+
+impl Default for OutputTransform {
+    /// sRGB encoding of clamped colors without exposure adjustment
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            tone_mapping: ToneMapping::Clamp,
+            transfer_function: TransferFunction::Srgb,
+        }
+    }
+}
+
+impl OutputTransform {
+    pub fn new(
+        exposure: f64,
+        tone_mapping: ToneMapping,
+        transfer_function: TransferFunction,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            exposure,
+            tone_mapping,
+            transfer_function,
+        }
+    }
+
+    /// Transforms the linear color into the encoded color (0.0-1.0 range for each color component)
+    pub fn apply(&self, color: &Vector) -> Vector {
+        let exposure_scale = 2.0_f64.powf(self.exposure);
+
+        let transform = |x: f64| -> f64 {
+            self.transfer_function
+                .encode(self.tone_mapping.map(x * exposure_scale))
+        };
+
+        Vector::new(
+            transform(color.x()),
+            transform(color.y()),
+            transform(color.z()),
+        )
+    }
+
+    /// Transforms the linear color into the 8-bit color
+    pub fn to_color(&self, color: &Vector) -> Color {
+        Color::from(self.apply(color))
+    }
+}
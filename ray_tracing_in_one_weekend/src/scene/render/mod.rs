@@ -1,8 +1,18 @@
-use rand::Rng;
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicU16, Ordering},
+    thread,
+};
 
-use crate::vector::{Ray, Vector};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
 
-use super::{utils::sort_vectors_by_z::sort_enumerated_vectors_by_z, Color, Scene};
+use crate::vector::{sampling::sample_uniform_disk, Ray, Vector};
+
+use super::{framebuffer::Framebuffer, materials::Material, scene_objects::HitRecord, Scene};
+
+/// Minimal ray parameter `t` of an intersection
+/// (prevents a scattered ray from hitting the surface it starts from due to floating point errors)
+pub(crate) const RAY_T_MIN: f64 = 0.0001;
 
 /// Result render image's shape
 pub struct ImageShape {
@@ -12,15 +22,19 @@ pub struct ImageShape {
 }
 
 impl ImageShape {
-    fn new(width: u16, aspect_ratio: f64) -> Self {
-        let height = u16::try_from(((width as f64) / aspect_ratio).round() as i64)
-            .expect("Image's height should be not greater than 65,536 px");
+    /// Returns the shape of the image with the given `width` and `aspect_ratio`
+    /// or `None` if its height is out of 1..=65535 range
+    fn new(width: u16, aspect_ratio: f64) -> Option<Self> {
+        let height = ((width as f64) / aspect_ratio).round();
+        if !(1.0..=u16::MAX as f64).contains(&height) {
+            return None;
+        }
 
-        Self {
+        Some(Self {
             _aspect_ratio: aspect_ratio,
             width,
-            height,
-        }
+            height: height as u16,
+        })
     }
 }
 
@@ -34,6 +48,10 @@ pub struct RenderParams {
     pub viewport_upper_left: Vector,
     /// Upper left pixel's location
     pub pixel00_loc: Vector,
+    /// Horizontal radius vector of the camera lens (defocus disk)
+    pub defocus_disk_u: Vector,
+    /// Vertical radius vector of the camera lens (defocus disk)
+    pub defocus_disk_v: Vector,
 }
 
 impl RenderParams {
@@ -42,12 +60,16 @@ impl RenderParams {
         pixel_delta_v: Vector,
         viewport_upper_left: Vector,
         pixel00_loc: Vector,
+        defocus_disk_u: Vector,
+        defocus_disk_v: Vector,
     ) -> Self {
         Self {
             pixel_delta_u,
             pixel_delta_v,
             viewport_upper_left,
             pixel00_loc,
+            defocus_disk_u,
+            defocus_disk_v,
         }
     }
 }
@@ -57,8 +79,6 @@ pub struct Renderer {
     params: Option<RenderParams>,
 }
 
-type RenderResult = Vec<Vec<[u8; 3]>>;
-
 impl Renderer {
     pub fn new(img_shape: Option<ImageShape>, params: Option<RenderParams>) -> Self {
         Self { img_shape, params }
@@ -75,55 +95,91 @@ impl Renderer {
 
 impl Scene {
     /// Populates render params if they are not defined or reconstructs them if image width has changed
-    fn prepare_render(&mut self, img_width: u16) {
+    ///
+    /// Returns `None` if the image's height is out of range (see `ImageShape::new`)
+    fn prepare_render(&mut self, img_width: u16) -> Option<()> {
         if self.renderer.params.is_none()
-            || self.renderer.img_shape.take().unwrap().width != img_width
+            || self
+                .renderer
+                .img_shape
+                .as_ref()
+                .map_or(true, |img_shape| img_shape.width != img_width)
         {
-            let img_shape = ImageShape::new(img_width, self.camera.viewport.aspect_ratio);
+            self.renderer = Renderer::empty();
+            let img_shape = ImageShape::new(img_width, self.camera.viewport.aspect_ratio)?;
 
             let pixel_delta_u = self.camera.viewport.viewport_u / (img_shape.width as f64);
             let pixel_delta_v = self.camera.viewport.viewport_v / (img_shape.height as f64);
 
             let viewport_upper_left = self.camera.camera_center
-                - Vector::new(0.0, 0.0, self.camera.focal_length)
+                - self.camera.w * self.camera.focal_length
                 - self.camera.viewport.viewport_u / 2.0
                 - self.camera.viewport.viewport_v / 2.0;
             let pixel00_loc = viewport_upper_left + (pixel_delta_u + pixel_delta_v) / 2.0;
 
+            let defocus_radius =
+                self.camera.focal_length * (self.camera.defocus_angle.to_radians() / 2.0).tan();
+            let defocus_disk_u = self.camera.u * defocus_radius;
+            let defocus_disk_v = self.camera.v * defocus_radius;
+
             let render_params = Some(RenderParams::new(
                 pixel_delta_u,
                 pixel_delta_v,
                 viewport_upper_left,
                 pixel00_loc,
+                defocus_disk_u,
+                defocus_disk_v,
             ));
 
             self.renderer = Renderer::new(Some(img_shape), render_params);
         }
+
+        Some(())
     }
 
+    /// Renders the scene into an image with the given `img_width`
+    ///
+    /// Image rows (scanlines) are distributed across `threads_num` threads
+    /// (the number of available CPUs by default).
+    /// Each row uses its own random generator derived from `seed`
+    /// so the result is the same for a fixed seed regardless of the number of threads
+    /// (the seed is random by default).
+    ///
+    /// The result keeps linear high dynamic range colors:
+    /// use `Framebuffer::to_img_arr` to convert it into an 8-bit image
+    /// (`None` if the image's height derived from the camera's aspect ratio is out of 1..=65535 range)
     pub fn render(
         &mut self,
         img_width: u16,
         antialiasing_iters_: Option<u16>,
         reflection_max_iters_: Option<u16>,
-    ) -> Option<RenderResult> {
+        threads_num_: Option<NonZeroUsize>,
+        seed_: Option<u64>,
+    ) -> Option<Framebuffer> {
         let antialiasing_iters = antialiasing_iters_.unwrap_or(1);
         let is_antialiasing_enabled = antialiasing_iters > 1;
         let reflection_max_iters = reflection_max_iters_.unwrap_or(10);
+        let threads_num = threads_num_
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+        let seed = seed_.unwrap_or_else(|| rand::thread_rng().gen());
 
-        self.prepare_render(img_width);
+        self.prepare_render(img_width)?;
+        self.build_bvh();
 
         if let Renderer {
             img_shape: Some(img_shape),
             params: Some(params),
         } = &self.renderer
         {
-            // Returns delta vector from (0, 0, -focal_length) to pixel center
-            // If antialiasing is enabled (antialiasing_iters > 1)
-            // returns the vector to some random point inside pixel (not necessarily pixel center)
-            let get_pixel_vector = |x: u16, y: u16| -> Vector {
-                let mut rng = rand::thread_rng();
+            let is_defocus_enabled = self.camera.defocus_angle > 0.0;
 
+            // Returns ray from the camera center to pixel center
+            // If antialiasing is enabled (antialiasing_iters > 1)
+            // returns the ray to some random point inside pixel (not necessarily pixel center)
+            // If defocus is enabled (defocus_angle > 0)
+            // the ray starts at some random point on the camera lens disk
+            let get_pixel_ray = |x: u16, y: u16, rng: &mut dyn RngCore| -> Ray {
                 let pixel_center = params.pixel00_loc
                     + params.pixel_delta_u
                         * (x as f64
@@ -139,89 +195,218 @@ impl Scene {
                             } else {
                                 0.0
                             });
-                let vector = pixel_center - self.camera.camera_center;
 
-                vector
+                let ray_origin = if is_defocus_enabled {
+                    // the point on the unit disk in the xy plane
+                    let p = sample_uniform_disk(&Vector::new(0.0, 0.0, 1.0), rng);
+
+                    self.camera.camera_center
+                        + params.defocus_disk_u * p.x()
+                        + params.defocus_disk_v * p.y()
+                } else {
+                    self.camera.camera_center
+                };
+
+                Ray::new(ray_origin, pixel_center - ray_origin)
             };
 
             // Returns color (0.0-1.0 range for each color component) for the given pixel (x, y)
-            let get_pixel_rgb = |x: u16, y: u16| -> Vector {
+            let get_pixel_rgb = |x: u16, y: u16, rng: &mut dyn RngCore| -> Vector {
                 let mut result = Vector::default();
 
                 // antialiasing cycle
                 for _ in 0..antialiasing_iters {
-                    let mut pixel_rgb_coeff: f64 = 1.0;
+                    // product of the attenuations of all the surfaces the ray has scattered from
+                    let mut pixel_attenuation = Vector::new(1.0, 1.0, 1.0);
+                    // light emitted along the path attenuated by the preceding surfaces
                     let mut pixel_rgb = Vector::default();
+                    // probability density of the previous surface's material choosing the ray's direction
+                    // (0.0 for the camera ray and the specular scattering)
+                    let mut scatter_pdf = 0.0;
 
-                    let mut ray = Ray::new(self.camera.camera_center, get_pixel_vector(x, y));
+                    let mut ray = get_pixel_ray(x, y, rng);
 
                     // reflection cycle
                     for _ in 0..reflection_max_iters {
-                        let mut objects_intersection_list = self
-                            .objects
-                            .iter()
-                            .enumerate()
-                            .filter_map(|(i, object)| {
-                                object
-                                    .calc_ray_intersection(&ray)
-                                    .and_then(|intersection_coords| Some((i, intersection_coords)))
-                            })
-                            .collect::<Vec<(usize, Vector)>>();
-
-                        sort_enumerated_vectors_by_z(objects_intersection_list.as_mut());
-
-                        if let Some((i, coords)) = objects_intersection_list.first() {
-                            // crutch for simple diffuse objects
-                            if self.objects[*i].get_diffusion() > 0.9 {
-                                pixel_rgb_coeff *= 0.5;
-                                let normal_vector = self.objects[*i].get_normal_vector(coords);
-
-                                ray = Ray::new(
-                                    *coords,
-                                    Vector::gen_rand_inside_hemisphere(Some(normal_vector)),
-                                );
-                            } else {
-                                let color = self.objects[*i].get_color(&ray);
-                                pixel_rgb =
-                                    Vector::new(color[0] as f64, color[1] as f64, color[2] as f64)
-                                        / 255.0;
+                        if let Some((object, hit)) =
+                            self.calc_closest_intersection(&ray, RAY_T_MIN..f64::INFINITY)
+                        {
+                            let material = object.get_material();
+
+                            // the emission is weighted against the light sampling
+                            // that could have chosen the same direction from the previous surface
+                            let emitted = material.emitted(&ray, &hit);
+                            if emitted.sq_norm() > 0.0 {
+                                let weight = if scatter_pdf > 0.0 {
+                                    get_power_heuristic(
+                                        scatter_pdf,
+                                        self.get_lights_pdf(&ray.origin(), &ray.direction()),
+                                    )
+                                } else {
+                                    1.0
+                                };
+
+                                pixel_rgb += pixel_attenuation * emitted * weight;
+                            }
 
-                                break;
+                            match material.scatter(&ray, &hit, rng) {
+                                Some((attenuation, scattered_ray)) => {
+                                    scatter_pdf =
+                                        material.pdf(&ray, &hit, &scattered_ray.direction());
+
+                                    // non-specular surfaces are lit by the lights directly
+                                    if scatter_pdf > 0.0 {
+                                        pixel_rgb += pixel_attenuation
+                                            * self.sample_lights(&ray, &hit, material, rng);
+                                    }
+
+                                    pixel_attenuation = pixel_attenuation * attenuation;
+                                    ray = scattered_ray;
+                                }
+                                // the ray is absorbed so the path gathers no more light
+                                None => break,
                             }
                         } else {
-                            // return background color if no object hit
-                            let background_coords = Vector::from(ray).normalize();
-                            let a = 0.5 * (background_coords.y() + 1.0);
-
-                            pixel_rgb = Vector::new(1.0, 1.0, 1.0) * (1.0 - a)
-                                + Vector::new(0.5, 0.7, 1.0) * a;
+                            // add environment color if no object hit
+                            pixel_rgb += pixel_attenuation * self.environment.get_color(&ray);
 
                             break;
                         };
                     }
 
-                    result = result + pixel_rgb * pixel_rgb_coeff
+                    result += pixel_rgb;
                 }
 
                 result / (antialiasing_iters as f64)
             };
 
-            // Creates an image representation in array (`Vec<Vec<[u8; 3]>>`)
-            // with given `img_width` x `img_height` size
-            // calling get_pixel_rgb to obtain each pixel data
-            let img_arr = (0..img_shape.height)
-                .into_iter()
-                .map(|y| {
-                    (0..img_shape.width)
-                        .into_iter()
-                        .map(|x| Color::from(get_pixel_rgb(x, y)))
-                        .collect::<Vec<Color>>()
-                })
-                .collect::<Vec<Vec<Color>>>();
-
-            Some(img_arr)
+            // Renders the row `y` with the random generator seeded by the row index
+            let render_row = |y: u16| -> Vec<Vector> {
+                let mut rng =
+                    StdRng::seed_from_u64(seed ^ (y as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+                (0..img_shape.width)
+                    .map(|x| get_pixel_rgb(x, y, &mut rng))
+                    .collect::<Vec<Vector>>()
+            };
+
+            // Creates a framebuffer with given `img_width` x `img_height` size:
+            // each thread takes the next unrendered row until all the rows are rendered
+            let next_row = AtomicU16::new(0);
+            let mut framebuffer =
+                Framebuffer::new(img_shape.width as usize, img_shape.height as usize);
+
+            thread::scope(|scope| {
+                let workers = (0..threads_num)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut rows = vec![];
+
+                            loop {
+                                let y = next_row.fetch_add(1, Ordering::Relaxed);
+                                if y >= img_shape.height {
+                                    break rows;
+                                }
+
+                                rows.push((y, render_row(y)));
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                for worker in workers {
+                    for (y, row) in worker.join().expect("Render thread panicked") {
+                        framebuffer.row_mut(y as usize).copy_from_slice(&row);
+                    }
+                }
+            });
+
+            Some(framebuffer)
         } else {
             None
         }
     }
+
+    /// Returns the probability density (per unit solid angle) of the light sampling
+    /// choosing `direction` (unit length) from the given `point`
+    fn get_lights_pdf(&self, point: &Vector, direction: &Vector) -> f64 {
+        if self.lights.is_empty() {
+            return 0.0;
+        }
+
+        self.lights
+            .iter()
+            .map(|light| light.pdf(point, direction))
+            .sum::<f64>()
+            / self.lights.len() as f64
+    }
+
+    /// Estimates the light arriving directly from a randomly chosen light
+    /// and scattered by the `material` along the incoming `ray` that hits the surface as described by `hit`.
+    ///
+    /// The estimate is weighted against the material's sampling
+    /// that may choose the same direction (multiple importance sampling).
+    /// Both strategies weigh a direction by the density of choosing it from any light
+    /// (see `get_lights_pdf`) so the weights sum up to 1 even if the lights overlap
+    fn sample_lights(
+        &self,
+        ray: &Ray,
+        hit: &HitRecord,
+        material: &dyn Material,
+        rng: &mut dyn RngCore,
+    ) -> Vector {
+        if self.lights.is_empty() {
+            return Vector::default();
+        }
+
+        let lights_num = self.lights.len() as f64;
+        let light = &self.lights[rng.gen_range(0..self.lights.len())];
+        let Some(sample) = light.sample(&hit.coordinates, rng) else {
+            return Vector::default();
+        };
+
+        let scattered = material.eval(ray, hit, &sample.direction);
+        if scattered.sq_norm() == 0.0 {
+            return Vector::default();
+        }
+
+        let shadow_ray = Ray::new(hit.coordinates, sample.direction);
+
+        if sample.pdf.is_none() {
+            // lights located at a single point cannot be chosen by the material's sampling,
+            // such a light is not seen if any object lies between the surface and the light
+            return if self
+                .calc_closest_intersection(&shadow_ray, RAY_T_MIN..sample.distance - RAY_T_MIN)
+                .is_some()
+            {
+                Vector::default()
+            } else {
+                scattered * sample.radiance * lights_num
+            };
+        }
+
+        let light_pdf = self.get_lights_pdf(&hit.coordinates, &sample.direction);
+        if light_pdf <= 0.0 {
+            return Vector::default();
+        }
+
+        // the light arriving along the direction is emitted by the closest surface
+        // which may be another light in front of the chosen one
+        let Some((object, light_hit)) =
+            self.calc_closest_intersection(&shadow_ray, RAY_T_MIN..f64::INFINITY)
+        else {
+            return Vector::default();
+        };
+        let emitted = object.get_material().emitted(&shadow_ray, &light_hit);
+
+        let weight = get_power_heuristic(light_pdf, material.pdf(ray, hit, &sample.direction));
+
+        scattered * emitted * (weight / light_pdf)
+    }
+}
+
+/// Returns the weight of the sample chosen by the strategy with the probability density `pdf`
+/// against the other strategy with the probability density `other_pdf` of choosing the same sample
+fn get_power_heuristic(pdf: f64, other_pdf: f64) -> f64 {
+    pdf.powi(2) / (pdf.powi(2) + other_pdf.powi(2))
 }
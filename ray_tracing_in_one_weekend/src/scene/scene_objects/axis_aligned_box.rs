@@ -0,0 +1,289 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Axis-aligned box object inside a scene
+///
+/// Surface coordinates of each face span the face's extent along its two axes,
+/// they are oriented counterclockwise around the face's outward normal
+pub struct AxisAlignedBox {
+    min: Vector,
+    max: Vector,
+    material: Arc<dyn Material>,
+}
+
+/// Returns the unit vector along the `axis` (0 for x, 1 for y, 2 for z) with the given `sign`
+fn get_axis_vector(axis: usize, sign: f64) -> Vector {
+    match axis {
+        0 => Vector::new(sign, 0.0, 0.0),
+        1 => Vector::new(0.0, sign, 0.0),
+        _ => Vector::new(0.0, 0.0, sign),
+    }
+}
+
+impl AxisAlignedBox {
+    /// Creates the box with the given opposite corners `a` and `b`
+    pub fn new(a: Vector, b: Vector, material: Arc<dyn Material>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            min: Vector::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: Vector::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z())),
+            material,
+        }
+    }
+
+    /// Computes the surface coordinates of the `point` on the face perpendicular to the `axis`
+    /// with the normal of the given `sign`
+    fn get_uv(&self, point: &Vector, axis: usize, sign: f64) -> (f64, f64) {
+        let scale = |axis: usize| -> f64 {
+            let size = self.max[axis] - self.min[axis];
+
+            if size > 0.0 {
+                ((point[axis] - self.min[axis]) / size).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        // `u` runs backwards on the negative faces to keep the coordinates counterclockwise
+        let u = scale((axis + 1) % 3);
+        let u = if sign > 0.0 { u } else { 1.0 - u };
+
+        (u, scale((axis + 2) % 3))
+    }
+
+    /// Computes the ray's entry and exit: the ray parameter `t`, the axis of the face
+    /// and the sign of its normal
+    fn calc_slab_hits(&self, ray: &Ray) -> Option<[(f64, usize, f64); 2]> {
+        // slab method: the ray is inside the box while it is between the planes of every axis,
+        // the entry is the latest plane entry and the exit is the earliest plane exit
+        // (the axis and the side of the normal are tracked along with them)
+        let origin = ray.origin();
+        let direction = ray.direction();
+
+        let mut entry = (f64::NEG_INFINITY, 0, 0.0);
+        let mut exit = (f64::INFINITY, 0, 0.0);
+
+        for axis in 0..3 {
+            let o = origin[axis];
+            let d = direction[axis];
+            let (min, max) = (self.min[axis], self.max[axis]);
+
+            // the ray is parallel to the planes
+            if d == 0.0 {
+                if o < min || o > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let (t_min, t_max) = ((min - o) / d, (max - o) / d);
+            let (t_near, t_far) = if d > 0.0 {
+                (t_min, t_max)
+            } else {
+                (t_max, t_min)
+            };
+
+            if t_near > entry.0 {
+                entry = (t_near, axis, -d.signum());
+            }
+            if t_far < exit.0 {
+                exit = (t_far, axis, d.signum());
+            }
+        }
+
+        if entry.0 > exit.0 {
+            return None;
+        }
+
+        Some([entry, exit])
+    }
+
+    fn get_hit_record(&self, ray: &Ray, (t, axis, sign): (f64, usize, f64)) -> HitRecord {
+        HitRecord::new(
+            ray,
+            t,
+            get_axis_vector(axis, sign),
+            self.get_uv(&ray.to_vector(t), axis, sign),
+        )
+        .with_tangent(&get_axis_vector((axis + 1) % 3, sign))
+    }
+}
+
+impl RayToObjectHandler for AxisAlignedBox {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.calc_slab_hits(ray)?
+            .into_iter()
+            .find(|(t, _, _)| t_range.contains(t))
+            .map(|hit| self.get_hit_record(ray, hit))
+    }
+
+    /// Returns the normal of the face closest to the given coordinates
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        (0..3)
+            .flat_map(|axis| {
+                let x = coordinates[axis];
+
+                [
+                    ((x - self.min[axis]).abs(), axis, -1.0),
+                    ((x - self.max[axis]).abs(), axis, 1.0),
+                ]
+            })
+            .min_by(|(lhs, _, _), (rhs, _, _)| lhs.total_cmp(rhs))
+            .map_or(Vector::default(), |(_, axis, sign)| {
+                get_axis_vector(axis, sign)
+            })
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(self.min, self.max))
+    }
+
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        let intervals = match self.calc_slab_hits(ray) {
+            Some([entry, exit]) if entry.0 < exit.0 => vec![RayInterval {
+                entry: self.get_hit_record(ray, entry),
+                exit: self.get_hit_record(ray, exit),
+            }],
+            _ => vec![],
+        };
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for AxisAlignedBox {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::materials::lambertian::Lambertian;
+
+    fn unit_box() -> AxisAlignedBox {
+        AxisAlignedBox::new(
+            Vector::new(-1.0, -2.0, -3.0),
+            Vector::new(1.0, 2.0, 3.0),
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    fn intersect(object: &AxisAlignedBox, origin: Vector, direction: Vector) -> Option<HitRecord> {
+        object.calc_ray_intersection(&Ray::new(origin, direction), 0.0..f64::INFINITY)
+    }
+
+    fn assert_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-18, "{lhs:?} != {rhs:?}");
+    }
+
+    #[test]
+    fn hit() {
+        let hit = intersect(
+            &unit_box(),
+            Vector::new(-5.0, 0.5, 0.5),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+
+        assert!((hit.t - 4.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(-1.0, 0.0, 0.0));
+        assert!(hit.is_front_face);
+    }
+
+    #[test]
+    fn miss() {
+        let object = unit_box();
+
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 2.5, 0.0),
+            Vector::new(1.0, 0.0, 0.0)
+        )
+        .is_none());
+        // the box is behind the ray
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 0.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn corner_graze_is_a_hit_without_an_interval() {
+        // the ray only touches the corner (1, 2, 3): entry and exit coincide
+        let object = unit_box();
+        let ray = Ray::new(Vector::new(-4.0, 7.0, 3.0), Vector::new(1.0, -1.0, 0.0));
+
+        let hit = object
+            .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+            .unwrap();
+
+        assert!((hit.t - 5.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        assert_close(hit.coordinates, Vector::new(1.0, 2.0, 3.0));
+        assert!(object.calc_ray_intervals(&ray).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ray_from_inside_hits_exit_face() {
+        let hit = intersect(&unit_box(), Vector::default(), Vector::new(0.0, 0.0, 1.0)).unwrap();
+
+        assert!((hit.t - 3.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 0.0, 1.0));
+        assert!(!hit.is_front_face);
+    }
+
+    #[test]
+    fn interval_spans_entry_and_exit() {
+        let ray = Ray::new(Vector::new(-5.0, 0.5, 0.5), Vector::new(1.0, 0.0, 0.0));
+        let intervals = unit_box().calc_ray_intervals(&ray).unwrap();
+
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].entry.t - 4.0).abs() < 1e-9);
+        assert!((intervals[0].exit.t - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tangents_follow_surface_coordinates_on_every_face() {
+        let object = unit_box();
+        let delta = 1e-3;
+
+        for axis in 0..3 {
+            for sign in [-1.0, 1.0] {
+                let normal = get_axis_vector(axis, sign);
+                // aim at a point off the face's center so both coordinates are inside (0, 1)
+                let target = normal * object.max[axis]
+                    + get_axis_vector((axis + 1) % 3, 0.3)
+                    + get_axis_vector((axis + 2) % 3, -0.2);
+                let hit_at = |offset: Vector| {
+                    let ray = Ray::new(target + offset + normal * 10.0, -normal);
+                    object
+                        .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+                        .unwrap()
+                };
+
+                let hit = hit_at(Vector::default());
+                assert!((hit.normal_vector - normal).sq_norm() < 1e-12);
+
+                let along_tangent = hit_at(hit.tangent * delta);
+                assert!(along_tangent.u > hit.u, "axis {axis}, sign {sign}");
+                assert!((along_tangent.v - hit.v).abs() < 1e-9);
+
+                let along_bitangent = hit_at(hit.bitangent() * delta);
+                assert!(along_bitangent.v > hit.v, "axis {axis}, sign {sign}");
+                assert!((along_bitangent.u - hit.u).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use std::{f64::consts::PI, ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{onb::Onb, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Cone object inside a scene going from the center of its base along the `axis` to the apex
+///
+/// Uncapped cones have no base. Surface coordinates of the side are the angle around the axis
+/// and the height along it, the base uses the angle and the distance from the center
+/// (all are scaled into 0.0-1.0 range)
+pub struct Cone {
+    base_center: Vector,
+    radius: f64,
+    height: f64,
+    is_capped: bool,
+    /// Basis with `w` along the cone's axis
+    basis: Onb,
+    material: Arc<dyn Material>,
+}
+
+impl Cone {
+    /// Creates the cone with non-zero `axis`, positive base `radius` and `height`
+    pub fn new(
+        base_center: Vector,
+        axis: Vector,
+        radius: f64,
+        height: f64,
+        is_capped: bool,
+        material: Arc<dyn Material>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(axis.sq_norm() > 0.0, "Cone's axis should not be zero");
+        assert!(
+            radius > 0.0 && height > 0.0,
+            "Cone's radius and height should be positive"
+        );
+
+        Self {
+            base_center,
+            radius,
+            height,
+            is_capped,
+            basis: Onb::new(&axis),
+            material,
+        }
+    }
+
+    /// Returns the local normal and the surface coordinates at the local point
+    /// of the side or the base (`is_base`)
+    fn get_local_normal_and_uv(&self, local: &Vector, is_base: bool) -> (Vector, (f64, f64)) {
+        let u = (local.y().atan2(local.x()) + PI) / (2.0 * PI);
+
+        if is_base {
+            return (
+                Vector::new(0.0, 0.0, -1.0),
+                (u, (local.x().hypot(local.y()) / self.radius).min(1.0)),
+            );
+        }
+
+        // gradient of x^2 + y^2 - k^2 (height - z)^2 with k = radius / height
+        let k = self.radius / self.height;
+        let normal_vector = Vector::new(local.x(), local.y(), k * k * (self.height - local.z()));
+
+        (
+            normal_vector.normalize(),
+            (u, (local.z() / self.height).clamp(0.0, 1.0)),
+        )
+    }
+
+    /// Computes the ray parameters `t` of all the intersections along the whole line
+    /// with the surface part they lie on
+    fn calc_hits(&self, ray: &Ray) -> Vec<(f64, bool)> {
+        // the ray is intersected in the cone's local coordinates (the axis is along z):
+        // 1. the side x^2 + y^2 = k^2 (height - z)^2 with k = radius / height and 0 <= z <= height
+        // gives the quadratic equation a t^2 + b t + c = 0 with
+        // a = d_x^2 + d_y^2 - k^2 d_z^2,
+        // b = 2 (o_x d_x + o_y d_y + k^2 (height - o_z) d_z),
+        // c = o_x^2 + o_y^2 - k^2 (height - o_z)^2
+        // 2. the base is the disk z = 0 of the given radius
+        let origin = self.basis.to_local(&(ray.origin() - self.base_center));
+        let direction = self.basis.to_local(&ray.direction());
+        let k2 = (self.radius / self.height).powi(2);
+        let h = self.height - origin.z();
+
+        // candidates (t, is_base)
+        let mut candidates: Vec<(f64, bool)> = vec![];
+
+        let a = direction.x().powi(2) + direction.y().powi(2) - k2 * direction.z().powi(2);
+        let b = 2.0
+            * (origin.x() * direction.x() + origin.y() * direction.y() + k2 * h * direction.z());
+        let c = origin.x().powi(2) + origin.y().powi(2) - k2 * h * h;
+
+        let roots = if a.abs() < 1e-12 {
+            // the ray is parallel to the side so it crosses the double cone once
+            if b != 0.0 {
+                vec![-c / b]
+            } else {
+                vec![]
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant >= 0.0 {
+                let sqrt_discriminant = discriminant.sqrt();
+                vec![
+                    (-b - sqrt_discriminant) / (2.0 * a),
+                    (-b + sqrt_discriminant) / (2.0 * a),
+                ]
+            } else {
+                vec![]
+            }
+        };
+
+        for t in roots {
+            // the other nappe of the double cone lies above the apex
+            let z = origin.z() + direction.z() * t;
+            if (0.0..=self.height).contains(&z) {
+                candidates.push((t, false));
+            }
+        }
+
+        if self.is_capped && direction.z() != 0.0 {
+            let t = -origin.z() / direction.z();
+            let (x, y) = (
+                origin.x() + direction.x() * t,
+                origin.y() + direction.y() * t,
+            );
+
+            if x * x + y * y <= self.radius.powi(2) {
+                candidates.push((t, true));
+            }
+        }
+
+        candidates
+    }
+
+    fn get_hit_record(&self, ray: &Ray, (t, is_base): (f64, bool)) -> HitRecord {
+        let local = self.basis.to_local(&(ray.to_vector(t) - self.base_center));
+        let (local_normal, uv) = self.get_local_normal_and_uv(&local, is_base);
+
+        // `u` is the angle around the axis
+        HitRecord::new(ray, t, self.basis.to_world(&local_normal), uv).with_tangent(
+            &self
+                .basis
+                .to_world(&Vector::new(-local.y(), local.x(), 0.0)),
+        )
+    }
+}
+
+impl RayToObjectHandler for Cone {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.calc_hits(ray)
+            .into_iter()
+            .filter(|(t, _)| t_range.contains(t))
+            .min_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs))
+            .map(|hit| self.get_hit_record(ray, hit))
+    }
+
+    /// Returns the normal of the side or the base closest to the given coordinates
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        let local = self.basis.to_local(&(coordinates - &self.base_center));
+
+        // distance to the side measured perpendicular to it
+        let side_distance = (local.x().hypot(local.y()) * self.height
+            - self.radius * (self.height - local.z()))
+        .abs()
+            / self.radius.hypot(self.height);
+        let is_base = self.is_capped && local.z().abs() < side_distance;
+
+        self.basis
+            .to_world(&self.get_local_normal_and_uv(&local, is_base).0)
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        // the base's extent along each axis is `radius * sin(angle between the axis and the cone's axis)`
+        let axis = self.basis.w();
+        let extent = Vector::new(
+            (1.0 - axis.x().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.y().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.z().powi(2)).max(0.0).sqrt(),
+        ) * self.radius;
+
+        Some(Aabb::from_points(&[
+            self.base_center - extent,
+            self.base_center + extent,
+            self.base_center + axis * self.height,
+        ]))
+    }
+
+    /// Only capped cones enclose a volume
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        if !self.is_capped {
+            return None;
+        }
+
+        // the cone is convex so the ray is inside it between the first and the last hits
+        let hits = self.calc_hits(ray);
+        let entry = hits.iter().min_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+        let exit = hits.iter().max_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+
+        let intervals = match entry.zip(exit) {
+            Some((entry, exit)) if entry.0 < exit.0 => vec![RayInterval {
+                entry: self.get_hit_record(ray, *entry),
+                exit: self.get_hit_record(ray, *exit),
+            }],
+            _ => vec![],
+        };
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Cone {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::materials::lambertian::Lambertian;
+
+    /// Cone with the base of radius 1 at z = 0 and the apex at z = 2
+    fn cone(is_capped: bool) -> Cone {
+        Cone::new(
+            Vector::default(),
+            Vector::new(0.0, 0.0, 1.0),
+            1.0,
+            2.0,
+            is_capped,
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    fn intersect(object: &Cone, origin: Vector, direction: Vector) -> Option<HitRecord> {
+        object.calc_ray_intersection(&Ray::new(origin, direction), 0.0..f64::INFINITY)
+    }
+
+    fn assert_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-18, "{lhs:?} != {rhs:?}");
+    }
+
+    #[test]
+    fn side_hit() {
+        // the radius is 0.5 halfway up, the side leans by the slope radius / height
+        let hit = intersect(
+            &cone(true),
+            Vector::new(-5.0, 0.0, 1.0),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+
+        assert!((hit.t - 4.5).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(-2.0, 0.0, 1.0).normalize());
+        assert!(hit.is_front_face);
+    }
+
+    #[test]
+    fn miss() {
+        let object = cone(true);
+
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 0.8, 1.0),
+            Vector::new(1.0, 0.0, 0.0)
+        )
+        .is_none());
+        // the other nappe of the double cone above the apex is not a part of the cone
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 0.0, 3.0),
+            Vector::new(1.0, 0.0, 0.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn tangent_graze_is_a_hit_without_an_interval() {
+        // the double root: the ray touches the circle of radius 0.5 halfway up
+        let object = cone(true);
+        let ray = Ray::new(Vector::new(-5.0, 0.5, 1.0), Vector::new(1.0, 0.0, 0.0));
+
+        let hit = object
+            .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+            .unwrap();
+
+        assert!((hit.t - 5.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 2.0, 1.0).normalize());
+        assert!(object.calc_ray_intervals(&ray).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ray_from_inside_hits_side() {
+        // the radius is 0.75 at z = 0.5
+        let hit = intersect(
+            &cone(true),
+            Vector::new(0.0, 0.0, 0.5),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+
+        assert!((hit.t - 0.75).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(2.0, 0.0, 1.0).normalize());
+        assert!(!hit.is_front_face);
+    }
+
+    #[test]
+    fn ray_along_axis_hits_base_only_if_capped() {
+        let (origin, direction) = (Vector::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        let hit = intersect(&cone(true), origin, direction).unwrap();
+        assert!((hit.t - 5.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 0.0, -1.0));
+
+        // through the open base the ray reaches the apex
+        let hit = intersect(&cone(false), origin, direction).unwrap();
+        assert!((hit.t - 7.0).abs() < 1e-6);
+        assert!(cone(false)
+            .calc_ray_intervals(&Ray::new(origin, direction))
+            .is_none());
+    }
+}
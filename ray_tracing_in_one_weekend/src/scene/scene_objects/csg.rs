@@ -0,0 +1,218 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler, SceneObject};
+
+/// Distance from the surface within which a point is considered lying on it
+const SURFACE_TOLERANCE: f64 = 1e-6;
+
+/// Boolean operation combining the volumes of two objects
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Points inside either object
+    #[default]
+    Union,
+    /// Points inside both objects
+    Intersection,
+    /// Points inside the first object but not inside the second one
+    Difference,
+}
+
+impl CsgOperation {
+    pub const ALL: [CsgOperation; 3] = [
+        CsgOperation::Union,
+        CsgOperation::Intersection,
+        CsgOperation::Difference,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CsgOperation::Union => "union",
+            CsgOperation::Intersection => "intersection",
+            CsgOperation::Difference => "difference",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|operation| operation.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the point is inside the combination given it is inside `lhs` or `rhs`
+    fn is_inside(&self, is_inside_lhs: bool, is_inside_rhs: bool) -> bool {
+        match self {
+            CsgOperation::Union => is_inside_lhs || is_inside_rhs,
+            CsgOperation::Intersection => is_inside_lhs && is_inside_rhs,
+            CsgOperation::Difference => is_inside_lhs && !is_inside_rhs,
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Constructive solid geometry object: the volumes of two objects combined by a boolean operation
+///
+/// Both objects must enclose a volume (see `RayToObjectHandler::calc_ray_intervals`),
+/// their materials are replaced by the combination's own one.
+/// The combination encloses a volume too so it can be combined further
+pub struct Csg {
+    operation: CsgOperation,
+    lhs: Arc<dyn SceneObject>,
+    rhs: Arc<dyn SceneObject>,
+    material: Arc<dyn Material>,
+}
+
+/// Returns the hit record of the same surface point with the opposite outward side
+fn flip(hit: HitRecord) -> HitRecord {
+    HitRecord {
+        normal_vector: -hit.normal_vector,
+        shading_normal: -hit.shading_normal,
+        is_front_face: !hit.is_front_face,
+        ..hit
+    }
+}
+
+impl Csg {
+    pub fn new(
+        operation: CsgOperation,
+        lhs: Arc<dyn SceneObject>,
+        rhs: Arc<dyn SceneObject>,
+        material: Arc<dyn Material>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            operation,
+            lhs,
+            rhs,
+            material,
+        }
+    }
+
+    /// Whether the surface of `rhs` bounding the combination faces into `rhs`
+    fn is_rhs_inverted(&self) -> bool {
+        self.operation == CsgOperation::Difference
+    }
+}
+
+impl RayToObjectHandler for Csg {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.calc_ray_intervals(ray)?
+            .into_iter()
+            .flat_map(|interval| [interval.entry, interval.exit])
+            .find(|hit| t_range.contains(&hit.t))
+    }
+
+    /// Returns the normal of the operand whose surface the given coordinates lie on
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        // the point lies on the operand's surface
+        // if the operand's interval along its normal starts or ends at the point
+        let is_on_surface = |object: &Arc<dyn SceneObject>, normal_vector: &Vector| {
+            object
+                .calc_ray_intervals(&Ray::new(*coordinates, *normal_vector))
+                .unwrap_or_default()
+                .iter()
+                .any(|interval| {
+                    interval.entry.t.abs() < SURFACE_TOLERANCE
+                        || interval.exit.t.abs() < SURFACE_TOLERANCE
+                })
+        };
+
+        let lhs_normal = self.lhs.get_normal_vector(coordinates);
+        if is_on_surface(&self.lhs, &lhs_normal) {
+            return lhs_normal;
+        }
+
+        let rhs_normal = self.rhs.get_normal_vector(coordinates);
+        match (
+            is_on_surface(&self.rhs, &rhs_normal),
+            self.is_rhs_inverted(),
+        ) {
+            (true, true) => -rhs_normal,
+            (true, false) => rhs_normal,
+            (false, _) => lhs_normal,
+        }
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        match self.operation {
+            CsgOperation::Union => self
+                .lhs
+                .get_bounding_box()
+                .zip(self.rhs.get_bounding_box())
+                .map(|(lhs, rhs)| lhs.union(&rhs)),
+            // the combination lies inside either operand
+            CsgOperation::Intersection => self
+                .lhs
+                .get_bounding_box()
+                .or_else(|| self.rhs.get_bounding_box()),
+            CsgOperation::Difference => self.lhs.get_bounding_box(),
+        }
+    }
+
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        // the bounds of both operands' intervals are swept along the ray
+        // tracking whether the ray is inside each operand,
+        // the combination's interval starts and ends where the operation's result changes
+        let lhs_intervals = self.lhs.calc_ray_intervals(ray)?;
+        let rhs_intervals = self.rhs.calc_ray_intervals(ray)?;
+
+        // bounds (hit, is_lhs, is_entry)
+        let mut bounds = lhs_intervals
+            .iter()
+            .flat_map(|interval| [(interval.entry, true, true), (interval.exit, true, false)])
+            .chain(rhs_intervals.iter().flat_map(|interval| {
+                [(interval.entry, false, true), (interval.exit, false, false)]
+            }))
+            .collect::<Vec<_>>();
+        bounds.sort_by(|(lhs, _, _), (rhs, _, _)| lhs.t.total_cmp(&rhs.t));
+
+        let mut intervals = vec![];
+        let (mut is_inside_lhs, mut is_inside_rhs) = (false, false);
+        let mut entry: Option<HitRecord> = None;
+
+        for (hit, is_lhs, is_entry) in bounds {
+            if is_lhs {
+                is_inside_lhs = is_entry;
+            } else {
+                is_inside_rhs = is_entry;
+            }
+
+            let hit = if !is_lhs && self.is_rhs_inverted() {
+                flip(hit)
+            } else {
+                hit
+            };
+
+            match (
+                entry,
+                self.operation.is_inside(is_inside_lhs, is_inside_rhs),
+            ) {
+                (None, true) => entry = Some(hit),
+                (Some(entry_hit), false) => {
+                    if entry_hit.t < hit.t {
+                        intervals.push(RayInterval {
+                            entry: entry_hit,
+                            exit: hit,
+                        });
+                    }
+                    entry = None;
+                }
+                _ => {}
+            }
+        }
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Csg {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
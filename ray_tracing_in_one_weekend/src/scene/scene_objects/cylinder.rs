@@ -0,0 +1,314 @@
+use std::{f64::consts::PI, ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{onb::Onb, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Cylinder object inside a scene going from the center of its base along the `axis` by `height`
+///
+/// Uncapped cylinders are open tubes. Surface coordinates of the side are the angle around the axis
+/// and the height along it, the caps use the angle and the distance from the center
+/// (all are scaled into 0.0-1.0 range)
+pub struct Cylinder {
+    base_center: Vector,
+    radius: f64,
+    height: f64,
+    is_capped: bool,
+    /// Basis with `w` along the cylinder's axis
+    basis: Onb,
+    material: Arc<dyn Material>,
+}
+
+impl Cylinder {
+    /// Creates the cylinder with non-zero `axis`, positive `radius` and `height`
+    pub fn new(
+        base_center: Vector,
+        axis: Vector,
+        radius: f64,
+        height: f64,
+        is_capped: bool,
+        material: Arc<dyn Material>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(axis.sq_norm() > 0.0, "Cylinder's axis should not be zero");
+        assert!(
+            radius > 0.0 && height > 0.0,
+            "Cylinder's radius and height should be positive"
+        );
+
+        Self {
+            base_center,
+            radius,
+            height,
+            is_capped,
+            basis: Onb::new(&axis),
+            material,
+        }
+    }
+
+    /// Returns the local normal and the surface coordinates at the local point
+    /// of the side (`cap` is `None`) or the cap at the base (`Some(false)`) or at the top (`Some(true)`)
+    fn get_local_normal_and_uv(&self, local: &Vector, cap: Option<bool>) -> (Vector, (f64, f64)) {
+        let u = (local.y().atan2(local.x()) + PI) / (2.0 * PI);
+
+        match cap {
+            None => (
+                Vector::new(local.x(), local.y(), 0.0) / self.radius,
+                (u, (local.z() / self.height).clamp(0.0, 1.0)),
+            ),
+            Some(is_top) => (
+                Vector::new(0.0, 0.0, if is_top { 1.0 } else { -1.0 }),
+                (u, (local.x().hypot(local.y()) / self.radius).min(1.0)),
+            ),
+        }
+    }
+
+    /// Computes the ray parameters `t` of all the intersections along the whole line
+    /// with the surface part they lie on
+    fn calc_hits(&self, ray: &Ray) -> Vec<(f64, Option<bool>)> {
+        // the ray is intersected in the cylinder's local coordinates (the axis is along z):
+        // 1. the side x^2 + y^2 = radius^2 with 0 <= z <= height gives the quadratic equation
+        // (d_x^2 + d_y^2) t^2 + 2 (o_x d_x + o_y d_y) t + o_x^2 + o_y^2 - radius^2 = 0
+        // 2. the caps are the disks z = 0 and z = height of the given radius
+        let origin = self.basis.to_local(&(ray.origin() - self.base_center));
+        let direction = self.basis.to_local(&ray.direction());
+
+        // candidates (t, cap)
+        let mut candidates: Vec<(f64, Option<bool>)> = vec![];
+
+        let a = direction.x().powi(2) + direction.y().powi(2);
+        let b = 2.0 * (origin.x() * direction.x() + origin.y() * direction.y());
+        let c = origin.x().powi(2) + origin.y().powi(2) - self.radius.powi(2);
+        let discriminant = b * b - 4.0 * a * c;
+
+        if a > 0.0 && discriminant >= 0.0 {
+            let sqrt_discriminant = discriminant.sqrt();
+
+            for t in [
+                (-b - sqrt_discriminant) / (2.0 * a),
+                (-b + sqrt_discriminant) / (2.0 * a),
+            ] {
+                let z = origin.z() + direction.z() * t;
+                if (0.0..=self.height).contains(&z) {
+                    candidates.push((t, None));
+                }
+            }
+        }
+
+        if self.is_capped && direction.z() != 0.0 {
+            for (z, is_top) in [(0.0, false), (self.height, true)] {
+                let t = (z - origin.z()) / direction.z();
+                let (x, y) = (
+                    origin.x() + direction.x() * t,
+                    origin.y() + direction.y() * t,
+                );
+
+                if x * x + y * y <= self.radius.powi(2) {
+                    candidates.push((t, Some(is_top)));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn get_hit_record(&self, ray: &Ray, (t, cap): (f64, Option<bool>)) -> HitRecord {
+        let local = self.basis.to_local(&(ray.to_vector(t) - self.base_center));
+        let (local_normal, uv) = self.get_local_normal_and_uv(&local, cap);
+
+        // `u` is the angle around the axis
+        HitRecord::new(ray, t, self.basis.to_world(&local_normal), uv).with_tangent(
+            &self
+                .basis
+                .to_world(&Vector::new(-local.y(), local.x(), 0.0)),
+        )
+    }
+}
+
+impl RayToObjectHandler for Cylinder {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.calc_hits(ray)
+            .into_iter()
+            .filter(|(t, _)| t_range.contains(t))
+            .min_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs))
+            .map(|hit| self.get_hit_record(ray, hit))
+    }
+
+    /// Returns the normal of the side or the cap closest to the given coordinates
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        let local = self.basis.to_local(&(coordinates - &self.base_center));
+
+        let side_distance = (local.x().hypot(local.y()) - self.radius).abs();
+        let cap_distance = local.z().abs().min((local.z() - self.height).abs());
+        let cap = (self.is_capped && cap_distance < side_distance)
+            .then_some(local.z() > self.height / 2.0);
+
+        self.basis
+            .to_world(&self.get_local_normal_and_uv(&local, cap).0)
+            .normalize()
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        // the caps' extent along each axis is `radius * sin(angle between the axis and the cylinder's axis)`
+        let axis = self.basis.w();
+        let extent = Vector::new(
+            (1.0 - axis.x().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.y().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.z().powi(2)).max(0.0).sqrt(),
+        ) * self.radius;
+        let top_center = self.base_center + axis * self.height;
+
+        Some(Aabb::from_points(&[
+            self.base_center - extent,
+            self.base_center + extent,
+            top_center - extent,
+            top_center + extent,
+        ]))
+    }
+
+    /// Only capped cylinders enclose a volume
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        if !self.is_capped {
+            return None;
+        }
+
+        // the cylinder is convex so the ray is inside it between the first and the last hits
+        let hits = self.calc_hits(ray);
+        let entry = hits.iter().min_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+        let exit = hits.iter().max_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+
+        let intervals = match entry.zip(exit) {
+            Some((entry, exit)) if entry.0 < exit.0 => vec![RayInterval {
+                entry: self.get_hit_record(ray, *entry),
+                exit: self.get_hit_record(ray, *exit),
+            }],
+            _ => vec![],
+        };
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Cylinder {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::materials::lambertian::Lambertian;
+
+    /// Cylinder of radius 1 from z = 0 to z = 2
+    fn cylinder(is_capped: bool) -> Cylinder {
+        Cylinder::new(
+            Vector::default(),
+            Vector::new(0.0, 0.0, 1.0),
+            1.0,
+            2.0,
+            is_capped,
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    fn intersect(object: &Cylinder, origin: Vector, direction: Vector) -> Option<HitRecord> {
+        object.calc_ray_intersection(&Ray::new(origin, direction), 0.0..f64::INFINITY)
+    }
+
+    fn assert_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-18, "{lhs:?} != {rhs:?}");
+    }
+
+    #[test]
+    fn side_hit() {
+        let hit = intersect(
+            &cylinder(true),
+            Vector::new(-5.0, 0.0, 1.0),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+
+        assert!((hit.t - 4.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(-1.0, 0.0, 0.0));
+        assert!(hit.is_front_face);
+    }
+
+    #[test]
+    fn miss() {
+        let object = cylinder(true);
+
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 1.5, 1.0),
+            Vector::new(1.0, 0.0, 0.0)
+        )
+        .is_none());
+        // passes above the top
+        assert!(intersect(
+            &object,
+            Vector::new(-5.0, 0.0, 2.5),
+            Vector::new(1.0, 0.0, 0.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn tangent_graze_is_a_hit_without_an_interval() {
+        // the double root: the ray touches the side along a single point
+        let object = cylinder(true);
+        let ray = Ray::new(Vector::new(-5.0, 1.0, 1.0), Vector::new(1.0, 0.0, 0.0));
+
+        let hit = object
+            .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+            .unwrap();
+
+        assert!((hit.t - 5.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 1.0, 0.0));
+        assert!(object.calc_ray_intervals(&ray).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ray_from_inside_hits_side() {
+        let hit = intersect(
+            &cylinder(true),
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+        .unwrap();
+
+        assert!((hit.t - 1.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(1.0, 0.0, 0.0));
+        assert!(!hit.is_front_face);
+    }
+
+    #[test]
+    fn ray_along_axis_hits_cap_only_if_capped() {
+        let (origin, direction) = (Vector::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, -1.0));
+
+        let hit = intersect(&cylinder(true), origin, direction).unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 0.0, 1.0));
+
+        // an open tube lets the ray through
+        assert!(intersect(&cylinder(false), origin, direction).is_none());
+    }
+
+    #[test]
+    fn only_capped_cylinder_has_intervals() {
+        let ray = Ray::new(Vector::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, -1.0));
+
+        let intervals = cylinder(true).calc_ray_intervals(&ray).unwrap();
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].entry.t - 3.0).abs() < 1e-9);
+        assert!((intervals[0].exit.t - 5.0).abs() < 1e-9);
+
+        assert!(cylinder(false).calc_ray_intervals(&ray).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use std::{f64::consts::PI, ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{onb::Onb, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Flat round disk object inside a scene
+///
+/// The outer side of the disk is the one `normal_vector` points to
+pub struct Disk {
+    center: Vector,
+    radius: f64,
+    /// Basis with `w` along the disk's normal vector, `u` and `v` lie in the disk's plane
+    basis: Onb,
+    material: Arc<dyn Material>,
+}
+
+impl Disk {
+    /// Creates the disk with non-zero `normal_vector`
+    pub fn new(
+        center: Vector,
+        normal_vector: Vector,
+        radius: f64,
+        material: Arc<dyn Material>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(
+            normal_vector.sq_norm() > 0.0,
+            "Disk's normal vector should not be zero"
+        );
+
+        Self {
+            center,
+            radius,
+            basis: Onb::new(&normal_vector),
+            material,
+        }
+    }
+}
+
+impl RayToObjectHandler for Disk {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        // the ray hits the disk's plane the same way as an infinite plane,
+        // the hit point should lie within `radius` from the center
+        let normal_vector = self.basis.w();
+        let denominator = normal_vector.dot(&ray.direction());
+
+        // the ray is parallel to the disk
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+
+        let t = normal_vector.dot(&(self.center - ray.origin())) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let local = self.basis.to_local(&(ray.to_vector(t) - self.center));
+        let distance = local.x().hypot(local.y());
+        if distance > self.radius {
+            return None;
+        }
+
+        // `u` is the angle around the center, `v` is the distance from the center
+        // (both are scaled into 0.0-1.0 range), the angle goes clockwise in the local basis
+        // for the coordinates to be counterclockwise around the normal
+        let phi = PI - local.y().atan2(local.x());
+        let tangent = if distance > 1e-12 * self.radius {
+            self.basis
+                .to_world(&Vector::new(local.y(), -local.x(), 0.0))
+        } else {
+            self.basis.u()
+        };
+
+        Some(
+            HitRecord::new(
+                ray,
+                t,
+                normal_vector,
+                (phi / (2.0 * PI), distance / self.radius),
+            )
+            .with_tangent(&tangent),
+        )
+    }
+
+    fn get_normal_vector(&self, _coordinates: &Vector) -> Vector {
+        self.basis.w()
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        // the disk's extent along each axis is `radius * sin(angle between the axis and the normal)`
+        let normal_vector = self.basis.w();
+        let extent = Vector::new(
+            (1.0 - normal_vector.x().powi(2)).max(0.0).sqrt(),
+            (1.0 - normal_vector.y().powi(2)).max(0.0).sqrt(),
+            (1.0 - normal_vector.z().powi(2)).max(0.0).sqrt(),
+        ) * self.radius;
+
+        Some(Aabb::new(self.center - extent, self.center + extent))
+    }
+}
+
+impl ObjectAppearance for Disk {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::materials::lambertian::Lambertian;
+
+    fn tilted_disk() -> Disk {
+        Disk::new(
+            Vector::new(1.0, 2.0, 3.0),
+            Vector::new(1.0, 2.0, -0.5),
+            2.0,
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    fn hit_at(disk: &Disk, point: Vector) -> HitRecord {
+        let normal = disk.basis.w();
+        let ray = Ray::new(point + normal * 10.0, -normal);
+
+        disk.calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+            .unwrap()
+    }
+
+    #[test]
+    fn tangents_follow_surface_coordinates() {
+        let disk = tilted_disk();
+        let delta = 1e-4;
+
+        for angle in [0.3_f64, 1.9, 3.5, 5.2] {
+            let point = disk.center
+                + disk
+                    .basis
+                    .to_world(&Vector::new(angle.cos(), angle.sin(), 0.0));
+            let hit = hit_at(&disk, point);
+
+            let along_tangent = hit_at(&disk, point + hit.tangent * delta);
+            assert!(along_tangent.u > hit.u, "angle {angle}");
+            assert!((along_tangent.v - hit.v).abs() < 1e-6);
+
+            let along_bitangent = hit_at(&disk, point + hit.bitangent() * delta);
+            assert!(along_bitangent.v > hit.v, "angle {angle}");
+            assert!((along_bitangent.u - hit.u).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn tangent_at_center_is_defined() {
+        let disk = tilted_disk();
+        let hit = hit_at(&disk, disk.center);
+
+        assert!((hit.tangent - disk.basis.u()).sq_norm() < 1e-12);
+        assert!((hit.bitangent().sq_norm() - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,29 +1,152 @@
+pub mod axis_aligned_box;
+pub mod cone;
+pub mod csg;
+pub mod cylinder;
+pub mod disk;
+pub mod plane;
+pub mod quad;
 pub mod sphere;
+pub mod torus;
+pub mod transformed;
+pub mod triangle;
+pub mod triangle_mesh;
 
-use crate::vector::{Ray, Vector};
+use std::ops::Range;
 
-use super::Color;
+use crate::vector::{onb::Onb, Ray, Vector};
+
+use super::{bvh::aabb::Aabb, materials::Material};
+
+/// Describes the intersection between a ray and a scene object
+#[derive(Clone, Copy, Debug)]
+pub struct HitRecord {
+    /// Ray parameter at the intersection: `_r_(t) = _origin_ + t * _direction_`
+    pub t: f64,
+    /// Intersection coordinates
+    pub coordinates: Vector,
+    /// Normal vector (unit length) pointing outwards of the object's surface
+    pub normal_vector: Vector,
+    /// Whether the ray hits the outer side of the object's surface
+    pub is_front_face: bool,
+    /// Normal vector (unit length) pointing outwards used for shading:
+    /// the geometric one unless the surface is smoothed (vertex normals)
+    /// or perturbed (see `materials::normal_mapped`)
+    pub shading_normal: Vector,
+    /// Tangent vector (unit length, perpendicular to `shading_normal`)
+    /// pointing towards increasing `u` where the object provides it
+    pub tangent: Vector,
+    /// Surface coordinates (both are in 0.0-1.0 range)
+    pub u: f64,
+    pub v: f64,
+}
+
+impl HitRecord {
+    /// Creates hit record for the given ray at the given `t`
+    /// with `normal_vector` (unit length) pointing outwards of the object's surface
+    pub fn new(ray: &Ray, t: f64, normal_vector: Vector, (u, v): (f64, f64)) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            t,
+            coordinates: ray.to_vector(t),
+            normal_vector,
+            is_front_face: ray.direction().dot(&normal_vector) < 0.0,
+            shading_normal: normal_vector,
+            tangent: Onb::new(&normal_vector).u(),
+            u,
+            v,
+        }
+    }
+
+    /// Returns the hit record with the tangent along the given direction of increasing `u`
+    /// (its part along the shading normal is dropped, an arbitrary tangent replaces a degenerate one)
+    pub fn with_tangent(self, direction: &Vector) -> Self {
+        let tangent = direction - &(self.shading_normal * direction.dot(&self.shading_normal));
+
+        Self {
+            tangent: if tangent.sq_norm() > 1e-12 * direction.sq_norm() {
+                tangent.normalize()
+            } else {
+                Onb::new(&self.shading_normal).u()
+            },
+            ..self
+        }
+    }
+
+    /// Returns the hit record with the given shading normal (unit length) pointing outwards,
+    /// the tangent is kept perpendicular to it
+    pub fn with_shading_normal(self, shading_normal: &Vector) -> Self {
+        Self {
+            shading_normal: *shading_normal,
+            ..self
+        }
+        .with_tangent(&self.tangent)
+    }
+
+    /// Bitangent vector (unit length) pointing towards increasing `v`
+    /// for the surface coordinates oriented counterclockwise around the shading normal
+    pub fn bitangent(&self) -> Vector {
+        self.shading_normal.cross(&self.tangent)
+    }
+
+    /// Normal vector (unit length) pointing against the ray
+    pub fn face_normal(&self) -> Vector {
+        if self.is_front_face {
+            self.normal_vector
+        } else {
+            self.normal_vector * -1.0
+        }
+    }
+
+    /// Shading normal vector (unit length) on the side of the surface the ray comes from
+    pub fn face_shading_normal(&self) -> Vector {
+        if self.is_front_face {
+            self.shading_normal
+        } else {
+            -self.shading_normal
+        }
+    }
+}
+
+/// Span of a ray lying inside a solid object
+/// between the hits where the ray enters and exits the object's surface
+#[derive(Clone, Copy, Debug)]
+pub struct RayInterval {
+    pub entry: HitRecord,
+    pub exit: HitRecord,
+}
 
 /// Describes the interaction between a ray from a pixel and an object in a scene
 pub trait RayToObjectHandler {
-    /// Returns a list of intersection coordinates (coordinates are represented as `Vector`)
-    /// between the given ray from the pixel (which is defined by `pixel_vec`) and a scene object.
-    ///
-    /// The intersection coordinates are sorted within the list by z-axis coordinate
-    fn calc_ray_intersection(&self, ray: &Ray) -> Option<Vector>;
+    /// Returns the nearest intersection between the given ray and a scene object
+    /// with the ray parameter `t` lying within `t_range`
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord>;
 
     /// Computes normal vector (unit length) at the given coordinates
     fn get_normal_vector(&self, coordinates: &Vector) -> Vector;
+
+    /// Returns the axis-aligned box bounding the object
+    /// or `None` if the object is unbounded
+    fn get_bounding_box(&self) -> Option<Aabb>;
+
+    /// Returns all the spans (disjoint and sorted by `t`) of the whole line along the given ray
+    /// lying inside the object or `None` if the object does not enclose a volume
+    ///
+    /// Only the objects enclosing a volume can be combined by constructive solid geometry (see `csg` module)
+    fn calc_ray_intervals(&self, _ray: &Ray) -> Option<Vec<RayInterval>> {
+        None
+    }
 }
 
 /// Describes an object's appearance
 pub trait ObjectAppearance {
-    /// Returns an object's color at the given pixel_vec
-    fn get_color(&self, ray: &Ray) -> Color;
-
-    fn get_diffusion(&self) -> f64;
+    /// Returns the material of an object's surface
+    fn get_material(&self) -> &dyn Material;
 }
 
 /// Trait for an abstract scene object
-pub trait SceneObject: RayToObjectHandler + ObjectAppearance {}
-impl<T: RayToObjectHandler + ObjectAppearance> SceneObject for T {}
+///
+/// Scene objects are shared between render threads
+pub trait SceneObject: RayToObjectHandler + ObjectAppearance + Send + Sync {}
+impl<T: RayToObjectHandler + ObjectAppearance + Send + Sync> SceneObject for T {}
@@ -0,0 +1,84 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{onb::Onb, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Infinite plane object inside a scene passing through the `point`
+///
+/// The outer side of the plane is the one `normal_vector` points to.
+/// Surface coordinates repeat every unit of length along the plane
+pub struct Plane {
+    point: Vector,
+    /// Basis with `w` along the plane's normal vector, `u` and `v` lie in the plane
+    basis: Onb,
+    material: Arc<dyn Material>,
+}
+
+impl Plane {
+    /// Creates the plane with non-zero `normal_vector`
+    pub fn new(point: Vector, normal_vector: Vector, material: Arc<dyn Material>) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(
+            normal_vector.sq_norm() > 0.0,
+            "Plane's normal vector should not be zero"
+        );
+
+        Self {
+            point,
+            basis: Onb::new(&normal_vector),
+            material,
+        }
+    }
+}
+
+impl RayToObjectHandler for Plane {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        // the ray hits the plane <_normal_, _r_ - _point_> = 0 at
+        // t = <_normal_, _point_ - _origin_> / <_normal_, _direction_>
+        let normal_vector = self.basis.w();
+        let denominator = normal_vector.dot(&ray.direction());
+
+        // the ray is parallel to the plane
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+
+        let t = normal_vector.dot(&(self.point - ray.origin())) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let local = self.basis.to_local(&(ray.to_vector(t) - self.point));
+
+        Some(
+            HitRecord::new(
+                ray,
+                t,
+                normal_vector,
+                (local.x().rem_euclid(1.0), local.y().rem_euclid(1.0)),
+            )
+            .with_tangent(&self.basis.u()),
+        )
+    }
+
+    fn get_normal_vector(&self, _coordinates: &Vector) -> Vector {
+        self.basis.w()
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+}
+
+impl ObjectAppearance for Plane {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
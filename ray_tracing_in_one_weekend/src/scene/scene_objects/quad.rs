@@ -0,0 +1,95 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Parallelogram object inside a scene spanned by the edges `u` and `v` from the `corner`
+///
+/// The outer side of the quad is the one `u x v` points to
+pub struct Quad {
+    corner: Vector,
+    u: Vector,
+    v: Vector,
+    /// Normal vector (unit length) of the quad's plane
+    normal_vector: Vector,
+    /// `(u x v) / ||u x v||^2` used to compute the planar coordinates of a hit point
+    w: Vector,
+    material: Arc<dyn Material>,
+}
+
+impl Quad {
+    /// Creates the quad with non-parallel edges `u` and `v`
+    pub fn new(corner: Vector, u: Vector, v: Vector, material: Arc<dyn Material>) -> Self
+    where
+        Self: Sized,
+    {
+        let n = u.cross(&v);
+        assert!(n.sq_norm() > 0.0, "Quad's edges should not be parallel");
+
+        Self {
+            corner,
+            u,
+            v,
+            normal_vector: n.normalize(),
+            w: n / n.sq_norm(),
+            material,
+        }
+    }
+}
+
+impl RayToObjectHandler for Quad {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        // 1. the ray hits the quad's plane <_normal_, _r_ - _corner_> = 0 at
+        // t = <_normal_, _corner_ - _origin_> / <_normal_, _direction_>
+        //
+        // 2. the hit point is expressed in the quad's edges: _r_ - _corner_ = alpha * _u_ + beta * _v_,
+        // crossing with `v` and `u` gives alpha = <_w_, _p_ x _v_>, beta = <_w_, _u_ x _p_>
+        //
+        // 3. the point lies inside the quad if and only if both alpha and beta are in 0.0-1.0 range
+        let denominator = self.normal_vector.dot(&ray.direction());
+
+        // the ray is parallel to the plane
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+
+        let t = self.normal_vector.dot(&(self.corner - ray.origin())) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let p = ray.to_vector(t) - self.corner;
+        let alpha = self.w.dot(&p.cross(&self.v));
+        let beta = self.w.dot(&self.u.cross(&p));
+
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+
+        Some(HitRecord::new(ray, t, self.normal_vector, (alpha, beta)).with_tangent(&self.u))
+    }
+
+    fn get_normal_vector(&self, _coordinates: &Vector) -> Vector {
+        self.normal_vector
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::from_points(&[
+            self.corner,
+            self.corner + self.u,
+            self.corner + self.v,
+            self.corner + self.u + self.v,
+        ]))
+    }
+}
+
+impl ObjectAppearance for Quad {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
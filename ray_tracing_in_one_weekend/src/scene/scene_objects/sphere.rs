@@ -1,48 +1,48 @@
+use std::{f64::consts::PI, ops::Range, sync::Arc};
+
 use crate::{
-    scene::utils::sort_vectors_by_z::sort_vectors_by_z,
+    scene::{
+        bvh::aabb::Aabb,
+        materials::{lambertian::Lambertian, Material},
+    },
     vector::{Ray, Vector},
 };
 
-use super::{Color, ObjectAppearance, RayToObjectHandler};
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler};
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 /// Sphere object inside a scene
 pub struct Sphere {
     center: Vector,
     radius: f64,
-    color: Color,
-    diffusion: f64,
+    material: Arc<dyn Material>,
 }
 
 impl Sphere {
-    pub fn new(center: Vector, radius: f64, color: Color, diffusion: Option<f64>) -> Self
+    pub fn new(center: Vector, radius: f64, material: Arc<dyn Material>) -> Self
     where
         Self: Sized,
     {
         Self {
             center,
             radius,
-            color,
-            diffusion: diffusion.unwrap_or(0.0),
+            material,
         }
     }
 
-    /// Default sphere object with unit radius centered at (0, 0, 0)
-    pub fn default() -> Self
-    where
-        Self: Sized,
-    {
-        Self {
-            center: Vector::default(),
-            radius: 1.0,
-            color: [255, 255, 255],
-            diffusion: 0.0,
-        }
+    /// Computes spherical surface coordinates of the point on the sphere
+    /// with the given outward normal vector:
+    /// `u` is the angle around the y-axis starting from -x,
+    /// `v` is the angle from -y to +y (both are scaled into 0.0-1.0 range)
+    fn get_uv(normal_vector: &Vector) -> (f64, f64) {
+        let theta = (-normal_vector.y()).clamp(-1.0, 1.0).acos();
+        let phi = (-normal_vector.z()).atan2(normal_vector.x()) + PI;
+
+        (phi / (2.0 * PI), theta / PI)
     }
-}
 
-impl RayToObjectHandler for Sphere {
-    fn calc_ray_intersection(&self, ray: &Ray) -> Option<Vector> {
+    /// Computes the ray parameters `t_1 <= t_2` where the ray intersects the sphere
+    fn calc_roots(&self, ray: &Ray) -> Option<(f64, f64)> {
         // 1. system of equations (underscores stand for vector):
         // --
         // |  radius^2 = ||_center_ - _r_||^2
@@ -63,6 +63,8 @@ impl RayToObjectHandler for Sphere {
         // _r_x = _origin_x + _direction_x * t
         // _r_y = _origin_y + _direction_y * t
         // _r_z = _origin_z + _direction_z * t
+        //
+        // 6. t_1 <= t_2 and the ray is inside the sphere between them
 
         let origin = ray.origin();
         let direction = ray.direction();
@@ -78,27 +80,71 @@ impl RayToObjectHandler for Sphere {
         }
 
         let t_1 = (-b - discriminant) / (2.0 * a);
+        let t_2 = (-b + discriminant) / (2.0 * a);
 
-        let intersection_coords: Option<Vector> = if t_1 > 0.0001 {
-            Some(origin + direction * t_1)
-        } else {
-            None
-        };
+        Some((t_1, t_2))
+    }
+
+    fn get_hit_record(&self, ray: &Ray, t: f64) -> HitRecord {
+        let normal_vector = self.get_normal_vector(&ray.to_vector(t));
+
+        // `u` grows counterclockwise around the y-axis when looking from its end
+        HitRecord::new(ray, t, normal_vector, Self::get_uv(&normal_vector))
+            .with_tangent(&Vector::new(normal_vector.z(), 0.0, -normal_vector.x()))
+    }
+}
 
-        intersection_coords
+impl Default for Sphere {
+    /// Default sphere object with unit radius centered at (0, 0, 0)
+    /// made of 50% grey lambertian material
+    fn default() -> Self {
+        Self {
+            center: Vector::default(),
+            radius: 1.0,
+            material: Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        }
+    }
+}
+
+impl RayToObjectHandler for Sphere {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        let (t_1, t_2) = self.calc_roots(ray)?;
+
+        // the nearest root within t_range is the first one that fits
+        [t_1, t_2]
+            .into_iter()
+            .find(|t| t_range.contains(t))
+            .map(|t| self.get_hit_record(ray, t))
     }
 
     fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
         (coordinates - &self.center).normalize()
     }
-}
 
-impl ObjectAppearance for Sphere {
-    fn get_color(&self, ray: &Ray) -> super::Color {
-        self.color
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        let radius_vector = Vector::new(self.radius, self.radius, self.radius);
+
+        Some(Aabb::new(
+            self.center - radius_vector,
+            self.center + radius_vector,
+        ))
     }
 
-    fn get_diffusion(&self) -> f64 {
-        self.diffusion
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        let intervals = match self.calc_roots(ray) {
+            Some((t_1, t_2)) if t_1 < t_2 => vec![RayInterval {
+                entry: self.get_hit_record(ray, t_1),
+                exit: self.get_hit_record(ray, t_2),
+            }],
+            _ => vec![],
+        };
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Sphere {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
     }
 }
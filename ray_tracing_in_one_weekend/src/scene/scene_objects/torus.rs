@@ -0,0 +1,264 @@
+use std::{f64::consts::PI, ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    utils::polynomial::find_real_roots,
+    vector::{onb::Onb, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Torus object inside a scene: the tube of `minor_radius` going around the circle of `major_radius`
+/// centered at `center` and perpendicular to the `axis`
+///
+/// Surface coordinates are the angle around the axis and the angle around the tube
+/// (both are scaled into 0.0-1.0 range)
+pub struct Torus {
+    center: Vector,
+    major_radius: f64,
+    minor_radius: f64,
+    /// Basis with `w` along the torus' axis
+    basis: Onb,
+    material: Arc<dyn Material>,
+}
+
+impl Torus {
+    /// Creates the torus with non-zero `axis` and positive radii
+    pub fn new(
+        center: Vector,
+        axis: Vector,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Arc<dyn Material>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(axis.sq_norm() > 0.0, "Torus' axis should not be zero");
+        assert!(
+            major_radius > 0.0 && minor_radius > 0.0,
+            "Torus' radii should be positive"
+        );
+
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+            basis: Onb::new(&axis),
+            material,
+        }
+    }
+
+    /// Returns the local normal at the local point:
+    /// the direction from the closest point of the tube's center circle
+    fn get_local_normal(&self, local: &Vector) -> Vector {
+        let radial = Vector::new(local.x(), local.y(), 0.0);
+        let circle_point = if radial.sq_norm() > 0.0 {
+            radial.normalize() * self.major_radius
+        } else {
+            radial
+        };
+
+        (local - &circle_point).normalize()
+    }
+
+    /// Computes the ray parameters `t` (in ascending order) within `t_range`
+    /// where the ray intersects the torus
+    fn calc_roots(&self, ray: &Ray, t_range: Range<f64>) -> Vec<f64> {
+        // the ray is intersected in the torus' local coordinates (the axis is along z):
+        // the torus (x^2 + y^2 + z^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + y^2)
+        // with the unit length ray direction gives the quartic equation
+        // (t^2 + 2 b t + c)^2 = 4 R^2 (e t^2 + 2 f t + g) with
+        // b = <o, d>, c = |o|^2 + R^2 - r^2, e = d_x^2 + d_y^2, f = o_x d_x + o_y d_y, g = o_x^2 + o_y^2
+        //
+        // the origin is moved to the ray's point closest to the center to keep the coefficients small
+        let origin = self.basis.to_local(&(ray.origin() - self.center));
+        let direction = self.basis.to_local(&ray.direction());
+
+        let outer_radius = self.major_radius + self.minor_radius;
+        let shift = -origin.dot(&direction);
+        let origin = origin + direction * shift;
+        // the ray misses the bounding sphere
+        if origin.sq_norm() > outer_radius.powi(2) {
+            return vec![];
+        }
+
+        let sq_major_radius = self.major_radius.powi(2);
+        let b = origin.dot(&direction);
+        let c = origin.sq_norm() + sq_major_radius - self.minor_radius.powi(2);
+        let e = direction.x().powi(2) + direction.y().powi(2);
+        let f = origin.x() * direction.x() + origin.y() * direction.y();
+        let g = origin.x().powi(2) + origin.y().powi(2);
+
+        let coefficients = [
+            c * c - 4.0 * sq_major_radius * g,
+            4.0 * b * c - 8.0 * sq_major_radius * f,
+            4.0 * b * b + 2.0 * c - 4.0 * sq_major_radius * e,
+            4.0 * b,
+            1.0,
+        ];
+
+        find_real_roots(
+            &coefficients,
+            (t_range.start - shift)..(t_range.end - shift),
+        )
+        .into_iter()
+        .map(|t| t + shift)
+        .collect()
+    }
+
+    fn get_hit_record(&self, ray: &Ray, t: f64) -> HitRecord {
+        let local = self.basis.to_local(&(ray.to_vector(t) - self.center));
+        let local_normal = self.get_local_normal(&local);
+
+        let u = (local.y().atan2(local.x()) + PI) / (2.0 * PI);
+        // angle around the tube is measured from the outer equator
+        let radial = Vector::new(local.x(), local.y(), 0.0);
+        let outward = if radial.sq_norm() > 0.0 {
+            radial.normalize().dot(&local_normal)
+        } else {
+            0.0
+        };
+        let v = (local_normal.z().atan2(outward) + PI) / (2.0 * PI);
+
+        HitRecord::new(ray, t, self.basis.to_world(&local_normal), (u, v)).with_tangent(
+            &self
+                .basis
+                .to_world(&Vector::new(-local.y(), local.x(), 0.0)),
+        )
+    }
+}
+
+impl RayToObjectHandler for Torus {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.calc_roots(ray, t_range)
+            .first()
+            .map(|t| self.get_hit_record(ray, *t))
+    }
+
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        self.basis
+            .to_world(&self.get_local_normal(&self.basis.to_local(&(coordinates - &self.center))))
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        // the torus' extent along each axis is
+        // `major_radius * sin(angle between the axis and the torus' axis) + minor_radius`
+        let axis = self.basis.w();
+        let extent = Vector::new(
+            (1.0 - axis.x().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.y().powi(2)).max(0.0).sqrt(),
+            (1.0 - axis.z().powi(2)).max(0.0).sqrt(),
+        ) * self.major_radius
+            + self.minor_radius;
+
+        Some(Aabb::new(self.center - extent, self.center + extent))
+    }
+
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        // the ray alternately enters and exits the torus,
+        // an odd number of roots means the ray touches the surface and it is treated as a miss
+        let roots = self.calc_roots(ray, f64::NEG_INFINITY..f64::INFINITY);
+        if roots.len() % 2 != 0 {
+            return Some(vec![]);
+        }
+
+        let intervals = roots
+            .chunks_exact(2)
+            .map(|bounds| RayInterval {
+                entry: self.get_hit_record(ray, bounds[0]),
+                exit: self.get_hit_record(ray, bounds[1]),
+            })
+            .collect();
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Torus {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::materials::lambertian::Lambertian;
+
+    /// Torus around the z axis with the radii 2 and 0.5
+    fn torus() -> Torus {
+        Torus::new(
+            Vector::default(),
+            Vector::new(0.0, 0.0, 1.0),
+            2.0,
+            0.5,
+            Arc::new(Lambertian::new(Vector::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    fn intersect(origin: Vector, direction: Vector) -> Option<HitRecord> {
+        torus().calc_ray_intersection(&Ray::new(origin, direction), 0.0..f64::INFINITY)
+    }
+
+    fn assert_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-12, "{lhs:?} != {rhs:?}");
+    }
+
+    #[test]
+    fn hit() {
+        let hit = intersect(Vector::new(-5.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0)).unwrap();
+
+        assert!((hit.t - 2.5).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(-1.0, 0.0, 0.0));
+        assert!(hit.is_front_face);
+    }
+
+    #[test]
+    fn miss() {
+        assert!(intersect(Vector::new(-5.0, 0.0, 1.0), Vector::new(1.0, 0.0, 0.0)).is_none());
+        assert!(intersect(Vector::new(-5.0, 3.0, 0.0), Vector::new(1.0, 0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn ray_through_hole_misses() {
+        assert!(intersect(Vector::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, -1.0)).is_none());
+        assert!(intersect(Vector::new(0.5, -0.5, 5.0), Vector::new(0.0, 0.0, -1.0)).is_none());
+    }
+
+    #[test]
+    fn tangent_graze_is_a_hit_without_an_interval() {
+        // the double root: the ray touches the outer equator at a single point
+        let ray = Ray::new(Vector::new(-5.0, 2.5, 0.0), Vector::new(1.0, 0.0, 0.0));
+
+        let hit = torus()
+            .calc_ray_intersection(&ray, 0.0..f64::INFINITY)
+            .unwrap();
+
+        assert!((hit.t - 5.0).abs() < 1e-6);
+        assert_close(hit.normal_vector, Vector::new(0.0, 1.0, 0.0));
+        assert!(torus().calc_ray_intervals(&ray).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ray_from_inside_hits_tube() {
+        let hit = intersect(Vector::new(2.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0)).unwrap();
+
+        assert!((hit.t - 0.5).abs() < 1e-9);
+        assert_close(hit.normal_vector, Vector::new(0.0, 0.0, 1.0));
+        assert!(!hit.is_front_face);
+    }
+
+    #[test]
+    fn ray_across_crosses_tube_twice() {
+        let ray = Ray::new(Vector::new(-5.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        let intervals = torus().calc_ray_intervals(&ray).unwrap();
+
+        assert_eq!(intervals.len(), 2);
+        for (interval, (entry, exit)) in intervals.iter().zip([(2.5, 3.5), (6.5, 7.5)]) {
+            assert!((interval.entry.t - entry).abs() < 1e-9);
+            assert!((interval.exit.t - exit).abs() < 1e-9);
+        }
+    }
+}
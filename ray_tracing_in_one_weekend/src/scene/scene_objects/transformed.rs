@@ -0,0 +1,144 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{mat3::Mat3, mat4::Mat4, Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayInterval, RayToObjectHandler, SceneObject};
+
+#[derive(Clone)]
+/// Instance of an object placed into a scene by an affine transform
+///
+/// The object is shared between its instances, each instance may replace its material
+pub struct Transformed {
+    object: Arc<dyn SceneObject>,
+    /// Transform from the object's coordinates into the world coordinates
+    matrix: Mat4,
+    inverse: Mat4,
+    /// Transform of normal vectors into the world coordinates (see `Mat3::normal_matrix`)
+    normal_matrix: Mat3,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl Transformed {
+    /// Creates the instance of the `object` transformed by the invertible `matrix`
+    /// (the object's material is used if `material` is `None`)
+    pub fn new(
+        object: Arc<dyn SceneObject>,
+        matrix: Mat4,
+        material: Option<Arc<dyn Material>>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let inverse = matrix
+            .inverse()
+            .expect("Transformed's matrix should be invertible");
+
+        Self {
+            object,
+            matrix,
+            inverse,
+            normal_matrix: matrix.to_mat3().normal_matrix(),
+            material,
+        }
+    }
+
+    /// Transforms the ray into the object's coordinates
+    /// returning it with the ratio of its `t` to the world ray's `t`
+    /// (the transform changes the direction's length which is normalized by the ray)
+    fn to_object_ray(&self, ray: &Ray) -> (Ray, f64) {
+        let direction = self.inverse.transform_vector(&ray.direction());
+
+        (
+            Ray::new(self.inverse.transform_point(&ray.origin()), direction),
+            direction.sq_norm().sqrt(),
+        )
+    }
+
+    /// Transforms the normal vector (unit length) of the object's surface into the world coordinates
+    fn to_world_normal(&self, normal_vector: &Vector) -> Vector {
+        self.normal_matrix
+            .transform_vector(normal_vector)
+            .normalize()
+    }
+
+    /// Transforms the object's hit record back into the world coordinates
+    fn to_world_hit(&self, ray: &Ray, hit: &HitRecord, t_scale: f64) -> HitRecord {
+        HitRecord::new(
+            ray,
+            hit.t / t_scale,
+            self.to_world_normal(&hit.normal_vector),
+            (hit.u, hit.v),
+        )
+        .with_shading_normal(&self.to_world_normal(&hit.shading_normal))
+        .with_tangent(&self.matrix.transform_vector(&hit.tangent))
+    }
+}
+
+impl RayToObjectHandler for Transformed {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        let (object_ray, t_scale) = self.to_object_ray(ray);
+
+        self.object
+            .calc_ray_intersection(
+                &object_ray,
+                (t_range.start * t_scale)..(t_range.end * t_scale),
+            )
+            .map(|hit| self.to_world_hit(ray, &hit, t_scale))
+    }
+
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        self.to_world_normal(
+            &self
+                .object
+                .get_normal_vector(&self.inverse.transform_point(coordinates)),
+        )
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        // the box bounding the transformed corners of the object's box
+        let bounding_box = self.object.get_bounding_box()?;
+        let (min, max) = (bounding_box.min(), bounding_box.max());
+
+        let corners = (0..8)
+            .map(|i| {
+                let corner = Vector::new(
+                    if i & 1 == 0 { min.x() } else { max.x() },
+                    if i & 2 == 0 { min.y() } else { max.y() },
+                    if i & 4 == 0 { min.z() } else { max.z() },
+                );
+
+                self.matrix.transform_point(&corner)
+            })
+            .collect::<Vec<_>>();
+
+        Some(Aabb::from_points(&corners))
+    }
+
+    fn calc_ray_intervals(&self, ray: &Ray) -> Option<Vec<RayInterval>> {
+        let (object_ray, t_scale) = self.to_object_ray(ray);
+
+        let intervals = self
+            .object
+            .calc_ray_intervals(&object_ray)?
+            .iter()
+            .map(|interval| RayInterval {
+                entry: self.to_world_hit(ray, &interval.entry, t_scale),
+                exit: self.to_world_hit(ray, &interval.exit, t_scale),
+            })
+            .collect();
+
+        Some(intervals)
+    }
+}
+
+impl ObjectAppearance for Transformed {
+    fn get_material(&self) -> &dyn Material {
+        match &self.material {
+            Some(material) => material.as_ref(),
+            None => self.object.get_material(),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{bvh::aabb::Aabb, materials::Material},
+    vector::{Ray, Vector},
+};
+
+use super::{HitRecord, ObjectAppearance, RayToObjectHandler};
+
+#[derive(Clone)]
+/// Triangle object inside a scene with the vertices `a`, `b` and `c`
+///
+/// The outer side of the triangle is the one its vertices are seen counterclockwise from.
+/// Surface coordinates are the barycentric coordinates of `b` and `c`
+/// (the vertices have (0, 0), (1, 0) and (0, 1) coordinates respectively)
+pub struct Triangle {
+    a: Vector,
+    /// Edges from `a` to `b` and from `a` to `c`
+    edge_ab: Vector,
+    edge_ac: Vector,
+    normal_vector: Vector,
+    material: Arc<dyn Material>,
+}
+
+impl Triangle {
+    /// Creates the non-degenerate triangle (its vertices should not lie on the same line)
+    pub fn new(a: Vector, b: Vector, c: Vector, material: Arc<dyn Material>) -> Self
+    where
+        Self: Sized,
+    {
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let n = edge_ab.cross(&edge_ac);
+        assert!(n.sq_norm() > 0.0, "Triangle should not be degenerate");
+
+        Self {
+            a,
+            edge_ab,
+            edge_ac,
+            normal_vector: n.normalize(),
+            material,
+        }
+    }
+}
+
+impl RayToObjectHandler for Triangle {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        let (t, beta, gamma) =
+            calc_triangle_intersection(ray, t_range, &self.a, &self.edge_ab, &self.edge_ac)?;
+
+        Some(HitRecord::new(ray, t, self.normal_vector, (beta, gamma)).with_tangent(&self.edge_ab))
+    }
+
+    fn get_normal_vector(&self, _coordinates: &Vector) -> Vector {
+        self.normal_vector
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::from_points(&[
+            self.a,
+            self.a + self.edge_ab,
+            self.a + self.edge_ac,
+        ]))
+    }
+}
+
+impl ObjectAppearance for Triangle {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
+
+/// Returns the ray parameter `t` within `t_range` and the barycentric coordinates (beta, gamma)
+/// of the intersection between the ray and the triangle with the vertex `a`
+/// and the edges `edge_ab` and `edge_ac` going out of it
+pub(crate) fn calc_triangle_intersection(
+    ray: &Ray,
+    t_range: Range<f64>,
+    a: &Vector,
+    edge_ab: &Vector,
+    edge_ac: &Vector,
+) -> Option<(f64, f64, f64)> {
+    // Moller-Trumbore algorithm: the hit point
+    // _origin_ + t * _direction_ = _a_ + beta * _ab_ + gamma * _ac_
+    // is solved for (t, beta, gamma) with Cramer's rule,
+    // the point lies inside the triangle if beta >= 0, gamma >= 0 and beta + gamma <= 1
+    let direction = ray.direction();
+    let p = direction.cross(edge_ac);
+    let determinant = edge_ab.dot(&p);
+
+    // the ray is parallel to the triangle (or the triangle is degenerate)
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    let inv_determinant = 1.0 / determinant;
+    let s = ray.origin() - *a;
+    let beta = s.dot(&p) * inv_determinant;
+    if !(0.0..=1.0).contains(&beta) {
+        return None;
+    }
+
+    let q = s.cross(edge_ab);
+    let gamma = direction.dot(&q) * inv_determinant;
+    if gamma < 0.0 || beta + gamma > 1.0 {
+        return None;
+    }
+
+    let t = edge_ac.dot(&q) * inv_determinant;
+    if !t_range.contains(&t) {
+        return None;
+    }
+
+    Some((t, beta, gamma))
+}
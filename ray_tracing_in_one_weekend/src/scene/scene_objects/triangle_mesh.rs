@@ -0,0 +1,182 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    scene::{
+        bvh::{aabb::Aabb, Bvh},
+        materials::Material,
+    },
+    vector::{Ray, Vector},
+};
+
+use super::{
+    triangle::calc_triangle_intersection, HitRecord, ObjectAppearance, RayToObjectHandler,
+};
+
+/// Vertex attributes shared between the meshes (e.g. the material groups of the same model)
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuffers {
+    pub positions: Vec<Vector>,
+    /// Vertex normals (unit length) used for smooth shading
+    pub normals: Vec<Vector>,
+    /// Vertex surface coordinates
+    pub uvs: Vec<(f64, f64)>,
+}
+
+/// Triangle of a mesh referring to the vertex attributes in `MeshBuffers`
+///
+/// The outer side of the face is the one its vertices are seen counterclockwise from
+#[derive(Clone, Copy, Debug)]
+pub struct MeshFace {
+    pub positions: [usize; 3],
+    /// Vertex normals' indices (the face is shaded flat without them)
+    pub normals: Option<[usize; 3]>,
+    /// Vertex surface coordinates' indices
+    /// (the barycentric coordinates are used as surface coordinates without them)
+    pub uvs: Option<[usize; 3]>,
+}
+
+/// Triangle mesh object inside a scene made of a single material
+///
+/// The faces are searched through the mesh's own bounding volume hierarchy
+pub struct TriangleMesh {
+    buffers: Arc<MeshBuffers>,
+    faces: Vec<MeshFace>,
+    bvh: Bvh,
+    bounding_box: Aabb,
+    material: Arc<dyn Material>,
+}
+
+impl TriangleMesh {
+    /// Creates the mesh with at least one face,
+    /// the faces' indices should lie within the corresponding `buffers`
+    pub fn new(buffers: Arc<MeshBuffers>, faces: Vec<MeshFace>, material: Arc<dyn Material>) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(!faces.is_empty(), "Mesh should have at least one face");
+        for face in &faces {
+            assert!(
+                face.positions.iter().all(|i| *i < buffers.positions.len())
+                    && face.normals.map_or(true, |normals| normals
+                        .iter()
+                        .all(|i| *i < buffers.normals.len()))
+                    && face
+                        .uvs
+                        .map_or(true, |uvs| uvs.iter().all(|i| *i < buffers.uvs.len())),
+                "Mesh face's vertex index is out of range"
+            );
+        }
+
+        let bounding_boxes = faces
+            .iter()
+            .map(|face| Aabb::from_points(&face.positions.map(|i| buffers.positions[i])))
+            .collect::<Vec<_>>();
+        let bounding_box = bounding_boxes
+            .iter()
+            .skip(1)
+            .fold(bounding_boxes[0], |acc, bounding_box| {
+                acc.union(bounding_box)
+            });
+        let bvh = Bvh::new(&bounding_boxes.into_iter().map(Some).collect::<Vec<_>>());
+
+        Self {
+            buffers,
+            faces,
+            bvh,
+            bounding_box,
+            material,
+        }
+    }
+
+    /// Returns the vertices of the face
+    fn get_vertices(&self, face: &MeshFace) -> [Vector; 3] {
+        face.positions.map(|i| self.buffers.positions[i])
+    }
+
+    fn calc_face_intersection(
+        &self,
+        face: &MeshFace,
+        ray: &Ray,
+        t_range: Range<f64>,
+    ) -> Option<HitRecord> {
+        let [a, b, c] = self.get_vertices(face);
+        let (edge_ab, edge_ac) = (b - a, c - a);
+        let (t, beta, gamma) = calc_triangle_intersection(ray, t_range, &a, &edge_ab, &edge_ac)?;
+        let alpha = 1.0 - beta - gamma;
+
+        // the tangent is the edges' combination along which only `u` changes:
+        // _ab_ = du_ab * _dp/du_ + dv_ab * _dp/dv_ (same for _ac_) solved for _dp/du_
+        let (uv, tangent) = match face.uvs {
+            Some(uvs) => {
+                let [uv_a, uv_b, uv_c] = uvs.map(|i| self.buffers.uvs[i]);
+                let (du_ab, dv_ab) = (uv_b.0 - uv_a.0, uv_b.1 - uv_a.1);
+                let (du_ac, dv_ac) = (uv_c.0 - uv_a.0, uv_c.1 - uv_a.1);
+                let determinant = du_ab * dv_ac - du_ac * dv_ab;
+
+                (
+                    (
+                        alpha * uv_a.0 + beta * uv_b.0 + gamma * uv_c.0,
+                        alpha * uv_a.1 + beta * uv_b.1 + gamma * uv_c.1,
+                    ),
+                    if determinant.abs() > 1e-12 {
+                        (edge_ab * dv_ac - edge_ac * dv_ab) / determinant
+                    } else {
+                        edge_ab
+                    },
+                )
+            }
+            None => ((beta, gamma), edge_ab),
+        };
+
+        let face_normal = edge_ab.cross(&edge_ac).normalize();
+        let mut hit = HitRecord::new(ray, t, face_normal, uv);
+
+        // the interpolated vertex normal is kept on the same side of the face as the face's normal
+        // so it agrees with the front face check of the geometry
+        if let Some(normals) = face.normals {
+            let [n_a, n_b, n_c] = normals.map(|i| self.buffers.normals[i]);
+            let normal_vector = (n_a * alpha + n_b * beta + n_c * gamma).normalize();
+
+            if normal_vector.dot(&face_normal) > 0.0 {
+                hit = hit.with_shading_normal(&normal_vector);
+            }
+        }
+
+        Some(hit.with_tangent(&tangent))
+    }
+}
+
+impl RayToObjectHandler for TriangleMesh {
+    fn calc_ray_intersection(&self, ray: &Ray, t_range: Range<f64>) -> Option<HitRecord> {
+        self.bvh
+            .calc_closest_intersection(ray, t_range, |i, t_range| {
+                self.calc_face_intersection(&self.faces[i], ray, t_range)
+            })
+            .map(|(_, hit)| hit)
+    }
+
+    /// Returns the flat normal of the face whose plane is the closest to the given coordinates
+    fn get_normal_vector(&self, coordinates: &Vector) -> Vector {
+        self.faces
+            .iter()
+            .map(|face| {
+                let [a, b, c] = self.get_vertices(face);
+                let normal_vector = (b - a).cross(&(c - a)).normalize();
+
+                (normal_vector.dot(&(coordinates - &a)).abs(), normal_vector)
+            })
+            .filter(|(distance, _)| !distance.is_nan())
+            .min_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs))
+            .map_or(Vector::default(), |(_, normal_vector)| normal_vector)
+    }
+
+    fn get_bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounding_box)
+    }
+}
+
+impl ObjectAppearance for TriangleMesh {
+    fn get_material(&self) -> &dyn Material {
+        self.material.as_ref()
+    }
+}
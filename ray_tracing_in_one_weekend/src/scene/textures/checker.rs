@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::vector::Vector;
+
+use super::Texture;
+
+/// Space filled with alternating cubes of two textures
+/// (the pattern depends on the hit point, not on the surface coordinates)
+pub struct Checker {
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+    /// Edge length of the cubes
+    scale: f64,
+}
+
+impl Checker {
+    /// Creates the checker of the cubes with positive edge length `scale` (1.0 by default)
+    pub fn new(even: Arc<dyn Texture>, odd: Arc<dyn Texture>, scale: Option<f64>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            even,
+            odd,
+            scale: scale.unwrap_or(1.0),
+        }
+    }
+}
+
+impl Texture for Checker {
+    fn get_color(&self, u: f64, v: f64, point: &Vector) -> Vector {
+        // the cube's parity is the parity of the sum of its integer coordinates
+        let cube = point / self.scale;
+        let sum = cube.x().floor() + cube.y().floor() + cube.z().floor();
+
+        if sum.rem_euclid(2.0) < 1.0 {
+            self.even.get_color(u, v, point)
+        } else {
+            self.odd.get_color(u, v, point)
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{scene::framebuffer::Framebuffer, vector::Vector};
+
+use super::Texture;
+
+/// Way the surface coordinates outside 0.0-1.0 range are mapped onto an image
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// The image is tiled
+    #[default]
+    Repeat,
+    /// The image's edge pixels are extended
+    Clamp,
+    /// The image is tiled with every other tile flipped
+    Mirror,
+}
+
+impl WrapMode {
+    pub const ALL: [WrapMode; 3] = [WrapMode::Repeat, WrapMode::Clamp, WrapMode::Mirror];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WrapMode::Repeat => "repeat",
+            WrapMode::Clamp => "clamp",
+            WrapMode::Mirror => "mirror",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|wrap_mode| wrap_mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Maps the pixel index into `0..size` range
+    fn wrap(&self, index: i64, size: i64) -> usize {
+        let index = match self {
+            WrapMode::Repeat => index.rem_euclid(size),
+            WrapMode::Clamp => index.clamp(0, size - 1),
+            WrapMode::Mirror => {
+                let index = index.rem_euclid(2 * size);
+
+                if index < size {
+                    index
+                } else {
+                    2 * size - 1 - index
+                }
+            }
+        };
+
+        index as usize
+    }
+}
+
+/// Texture mapping an image onto the surface coordinates:
+/// `u` goes from the image's left edge to the right one and `v` from the bottom edge to the top one
+pub struct ImageTexture {
+    image: Framebuffer,
+    wrap_mode: WrapMode,
+}
+
+impl ImageTexture {
+    /// Creates the texture from the non-empty `image` of linear colors
+    /// (the image is tiled by default)
+    pub fn new(image: Framebuffer, wrap_mode: Option<WrapMode>) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(!image.is_empty(), "Texture image should not be empty");
+
+        Self {
+            image,
+            wrap_mode: wrap_mode.unwrap_or_default(),
+        }
+    }
+}
+
+impl Texture for ImageTexture {
+    fn get_color(&self, u: f64, v: f64, _point: &Vector) -> Vector {
+        let width = self.image.width() as i64;
+        let height = self.image.height() as i64;
+
+        // image coordinates in pixels (pixel centers lie at half-integer coordinates)
+        let x = u * width as f64 - 0.5;
+        let y = (1.0 - v) * height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+
+        let get_pixel = |x: i64, y: i64| -> Vector {
+            *self.image.get_pixel(
+                self.wrap_mode.wrap(x, width),
+                self.wrap_mode.wrap(y, height),
+            )
+        };
+
+        // bilinear interpolation between the four nearest pixels
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let (x1, y1) = (x0.saturating_add(1), y0.saturating_add(1));
+        let top = get_pixel(x0, y0).lerp(&get_pixel(x1, y0), tx);
+        let bottom = get_pixel(x0, y1).lerp(&get_pixel(x1, y1), tx);
+
+        top.lerp(&bottom, ty)
+    }
+}
@@ -0,0 +1,14 @@
+pub mod checker;
+pub mod image_texture;
+pub mod noise;
+pub mod perlin;
+pub mod solid_color;
+
+use crate::vector::Vector;
+
+/// Describes the color varying over an object's surface
+pub trait Texture: Send + Sync {
+    /// Returns the color (0.0-1.0 range for each color component)
+    /// at the surface coordinates (`u`, `v`) of the hit `point`
+    fn get_color(&self, u: f64, v: f64, point: &Vector) -> Vector;
+}
@@ -0,0 +1,86 @@
+use crate::vector::Vector;
+
+use super::{perlin::Perlin, Texture};
+
+/// Seed of the noise's gradients (the textures look the same in every render)
+const NOISE_SEED: u64 = 0;
+
+/// Number of the noise octaves summed up by turbulence
+const TURBULENCE_DEPTH: usize = 7;
+
+/// Pattern made of Perlin noise
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoisePattern {
+    /// Smooth noise
+    #[default]
+    Noise,
+    /// Sum of the noise octaves
+    Turbulence,
+    /// Sine stripes along the z-axis distorted by turbulence (the scale sets the stripes' frequency only)
+    Marble,
+}
+
+impl NoisePattern {
+    pub const ALL: [NoisePattern; 3] = [
+        NoisePattern::Noise,
+        NoisePattern::Turbulence,
+        NoisePattern::Marble,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NoisePattern::Noise => "noise",
+            NoisePattern::Turbulence => "turbulence",
+            NoisePattern::Marble => "marble",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|pattern| pattern.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Procedural texture: the color scaled by the noise pattern at the hit point
+pub struct NoiseTexture {
+    perlin: Perlin,
+    pattern: NoisePattern,
+    /// Frequency of the pattern
+    scale: f64,
+    color: Vector,
+}
+
+impl NoiseTexture {
+    /// Creates the texture of the pattern with the given frequency `scale` (1.0 by default)
+    /// and `color` (white by default)
+    pub fn new(pattern: NoisePattern, scale: Option<f64>, color: Option<Vector>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            perlin: Perlin::new(NOISE_SEED),
+            pattern,
+            scale: scale.unwrap_or(1.0),
+            color: color.unwrap_or(Vector::new(1.0, 1.0, 1.0)),
+        }
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn get_color(&self, _u: f64, _v: f64, point: &Vector) -> Vector {
+        let scaled_point = point * self.scale;
+
+        let intensity = match self.pattern {
+            NoisePattern::Noise => 0.5 * (1.0 + self.perlin.noise(&scaled_point)),
+            NoisePattern::Turbulence => self.perlin.turbulence(&scaled_point, TURBULENCE_DEPTH),
+            NoisePattern::Marble => {
+                let turbulence = self.perlin.turbulence(point, TURBULENCE_DEPTH);
+
+                0.5 * (1.0 + (scaled_point.z() + 10.0 * turbulence).sin())
+            }
+        };
+
+        self.color * intensity.clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,81 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::vector::{sampling::sample_uniform_sphere, Vector};
+
+/// Number of the lattice's gradient vectors (the lattice repeats with this period)
+const POINTS_NUM: usize = 256;
+
+/// Perlin's gradient noise: smooth pseudorandom function of a point
+/// interpolating random gradients given at the integer lattice points
+pub struct Perlin {
+    gradients: Vec<Vector>,
+    /// Permutations of the gradients' indices along each axis
+    permutations: [Vec<usize>; 3],
+}
+
+impl Perlin {
+    /// Creates the noise with the gradients generated from the given `seed`
+    pub fn new(seed: u64) -> Self
+    where
+        Self: Sized,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let gradients = (0..POINTS_NUM)
+            .map(|_| sample_uniform_sphere(&mut rng))
+            .collect();
+        let mut gen_permutation = || {
+            let mut permutation = (0..POINTS_NUM).collect::<Vec<_>>();
+            permutation.shuffle(&mut rng);
+            permutation
+        };
+        let permutations = [gen_permutation(), gen_permutation(), gen_permutation()];
+
+        Self {
+            gradients,
+            permutations,
+        }
+    }
+
+    /// Returns the noise value (in -1.0..1.0 range) at the point
+    pub fn noise(&self, point: &Vector) -> f64 {
+        let floor = [point.x().floor(), point.y().floor(), point.z().floor()];
+        let fraction = [
+            point.x() - floor[0],
+            point.y() - floor[1],
+            point.z() - floor[2],
+        ];
+        let cell = floor.map(|x| (x as i64).rem_euclid(POINTS_NUM as i64) as usize);
+
+        // Hermite smoothing of the fractions removes grid artifacts
+        let [u, v, w] = fraction.map(|t| t * t * (3.0 - 2.0 * t));
+
+        // trilinear interpolation of the gradients' dot products with the offsets from the corners
+        let mut sum = 0.0;
+        for (i, j, k) in (0..8).map(|corner| (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1)) {
+            let index = self.permutations[0][(cell[0] + i) % POINTS_NUM]
+                ^ self.permutations[1][(cell[1] + j) % POINTS_NUM]
+                ^ self.permutations[2][(cell[2] + k) % POINTS_NUM];
+            let offset = Vector::new(
+                fraction[0] - i as f64,
+                fraction[1] - j as f64,
+                fraction[2] - k as f64,
+            );
+            let weight = |t: f64, corner: usize| if corner == 1 { t } else { 1.0 - t };
+
+            sum += weight(u, i) * weight(v, j) * weight(w, k) * self.gradients[index].dot(&offset);
+        }
+
+        sum
+    }
+
+    /// Returns the turbulence (non-negative) at the point:
+    /// the sum of `depth` noise octaves of doubling frequency and halving amplitude
+    pub fn turbulence(&self, point: &Vector, depth: usize) -> f64 {
+        let (sum, _, _) = (0..depth).fold((0.0, *point, 1.0), |(sum, point, weight), _| {
+            (sum + weight * self.noise(&point), point * 2.0, weight * 0.5)
+        });
+
+        f64::abs(sum)
+    }
+}
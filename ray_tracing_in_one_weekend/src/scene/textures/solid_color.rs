@@ -0,0 +1,23 @@
+use crate::vector::Vector;
+
+use super::Texture;
+
+/// Texture of a single color
+pub struct SolidColor {
+    color: Vector,
+}
+
+impl SolidColor {
+    pub fn new(color: Vector) -> Self
+    where
+        Self: Sized,
+    {
+        Self { color }
+    }
+}
+
+impl Texture for SolidColor {
+    fn get_color(&self, _u: f64, _v: f64, _point: &Vector) -> Vector {
+        self.color
+    }
+}
@@ -1 +0,0 @@
-pub mod sort_vectors_by_z;
@@ -0,0 +1,56 @@
+use std::{error::Error, fmt, io};
+
+/// Error occurred while reading or writing an image file
+#[derive(Debug)]
+pub enum ImageIoError {
+    /// The file's extension (or the requested format) is not supported
+    UnsupportedFormat(String),
+    /// The image has no pixels
+    EmptyImage,
+    /// The image's rows have different lengths
+    RaggedRows {
+        row: usize,
+        expected_width: usize,
+        actual_width: usize,
+    },
+    /// The file's contents are not a valid image of its format
+    InvalidData(String),
+    /// The file cannot be opened, read or written
+    Io(io::Error),
+}
+
+impl fmt::Display for ImageIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageIoError::UnsupportedFormat(format) => {
+                write!(f, "unsupported image format `{format}`")
+            }
+            ImageIoError::EmptyImage => write!(f, "image has no pixels"),
+            ImageIoError::RaggedRows {
+                row,
+                expected_width,
+                actual_width,
+            } => write!(
+                f,
+                "image row {row} has {actual_width} pixels, expected {expected_width}"
+            ),
+            ImageIoError::InvalidData(message) => write!(f, "{message}"),
+            ImageIoError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ImageIoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImageIoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImageIoError {
+    fn from(e: io::Error) -> Self {
+        ImageIoError::Io(e)
+    }
+}
@@ -1 +1,4 @@
+pub mod image_io_error;
+pub mod polynomial;
+pub mod read_img_arr_from_file;
 pub mod write_img_arr_to_file;
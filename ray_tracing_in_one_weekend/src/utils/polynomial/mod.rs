@@ -0,0 +1,189 @@
+use std::ops::Range;
+
+/// Evaluates the polynomial `coefficients[0] + coefficients[1] * x + coefficients[2] * x^2 + ...`
+pub fn eval_polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// Bounds the rounding error of `eval_polynomial` at `x`
+fn eval_error_bound(coefficients: &[f64], x: f64) -> f64 {
+    let magnitude = coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |acc, c| acc * x.abs() + c.abs());
+
+    2.0 * coefficients.len() as f64 * f64::EPSILON * magnitude
+}
+
+/// Finds the real roots within `range` of the polynomial
+/// `coefficients[0] + coefficients[1] * x + coefficients[2] * x^2 + ...` in ascending order
+///
+/// The roots are isolated between the roots of the polynomial's derivative
+/// (the polynomial is monotonic between them) and refined by bisection,
+/// multiple roots are returned once
+pub fn find_real_roots(coefficients: &[f64], range: Range<f64>) -> Vec<f64> {
+    // zero leading coefficients do not change the polynomial
+    let degree = match coefficients.iter().rposition(|c| *c != 0.0) {
+        Some(degree) if degree > 0 => degree,
+        _ => return vec![],
+    };
+    let coefficients = &coefficients[..=degree];
+
+    if degree == 1 {
+        let root = -coefficients[0] / coefficients[1];
+
+        return if range.contains(&root) {
+            vec![root]
+        } else {
+            vec![]
+        };
+    }
+
+    // every root lies within Cauchy's bound
+    let bound = 1.0
+        + coefficients[..degree]
+            .iter()
+            .map(|c| (c / coefficients[degree]).abs())
+            .fold(0.0, f64::max);
+    let start = range.start.max(-bound);
+    let end = range.end.min(bound);
+    if start >= end {
+        return vec![];
+    }
+
+    let derivative = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c * i as f64)
+        .collect::<Vec<_>>();
+
+    let mut bounds = vec![start];
+    bounds.extend(find_real_roots(&derivative, start..end));
+    bounds.push(end);
+
+    // values within the rounding error are zeros: a multiple root only touches zero
+    // at the derivative's root and would be missed or split in two by the rounding
+    let values = bounds
+        .iter()
+        .map(|x| match eval_polynomial(coefficients, *x) {
+            value if value.abs() <= eval_error_bound(coefficients, *x) => 0.0,
+            value => value,
+        })
+        .collect::<Vec<_>>();
+
+    let mut roots: Vec<f64> = vec![];
+    for (interval, interval_values) in bounds.windows(2).zip(values.windows(2)) {
+        let (mut a, mut b) = (interval[0], interval[1]);
+        let (mut p_a, p_b) = (interval_values[0], interval_values[1]);
+
+        let root = if p_a == 0.0 {
+            a
+        } else if p_b == 0.0 || p_a.signum() == p_b.signum() {
+            // the root at the interval's end is found by the next interval
+            continue;
+        } else {
+            // the interval always keeps the sign change
+            while b - a > f64::EPSILON * a.abs().max(b.abs()).max(1.0) {
+                let middle = 0.5 * (a + b);
+                let p_middle = eval_polynomial(coefficients, middle);
+
+                if p_middle == 0.0 {
+                    (a, b) = (middle, middle);
+                } else if p_middle.signum() == p_a.signum() {
+                    (a, p_a) = (middle, p_middle);
+                } else {
+                    b = middle;
+                }
+            }
+
+            0.5 * (a + b)
+        };
+
+        if range.contains(&root) && roots.last().map_or(true, |last| *last != root) {
+            roots.push(root);
+        }
+    }
+
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the coefficients (in ascending order) of the monic polynomial with the given roots
+    fn from_roots(roots: &[f64]) -> Vec<f64> {
+        roots.iter().fold(vec![1.0], |coefficients, root| {
+            // multiplies by (x - root)
+            let mut product = vec![0.0; coefficients.len() + 1];
+            for (i, c) in coefficients.iter().enumerate() {
+                product[i + 1] += c;
+                product[i] -= c * root;
+            }
+            product
+        })
+    }
+
+    fn assert_roots(coefficients: &[f64], range: Range<f64>, expected: &[f64]) {
+        let roots = find_real_roots(coefficients, range);
+
+        assert_eq!(roots.len(), expected.len(), "{roots:?} != {expected:?}");
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!((root - expected).abs() < 1e-9, "{roots:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn eval_polynomial_uses_ascending_coefficients() {
+        assert_eq!(eval_polynomial(&[1.0, -2.0, 3.0], 2.0), 9.0);
+        assert_eq!(eval_polynomial(&[], 2.0), 0.0);
+    }
+
+    #[test]
+    fn linear_and_constant() {
+        assert_roots(&[-3.0, 2.0], -10.0..10.0, &[1.5]);
+        assert_roots(&[-3.0, 2.0, 0.0, 0.0], -10.0..10.0, &[1.5]);
+        assert_roots(&[1.0], -10.0..10.0, &[]);
+        assert_roots(&[0.0, 0.0], -10.0..10.0, &[]);
+    }
+
+    #[test]
+    fn simple_roots() {
+        assert_roots(
+            &from_roots(&[-2.0, 0.5, 3.0]),
+            -10.0..10.0,
+            &[-2.0, 0.5, 3.0],
+        );
+        assert_roots(
+            &from_roots(&[-1.7, -0.3, 0.4, 2.9]),
+            -10.0..10.0,
+            &[-1.7, -0.3, 0.4, 2.9],
+        );
+        // x^2 + 1
+        assert_roots(&[1.0, 0.0, 1.0], -10.0..10.0, &[]);
+    }
+
+    #[test]
+    fn roots_outside_range_are_skipped() {
+        assert_roots(&from_roots(&[-2.0, 0.5, 3.0]), 0.0..2.0, &[0.5]);
+        // the range's end is excluded
+        assert_roots(&from_roots(&[-2.0, 0.5, 3.0]), -2.0..3.0, &[-2.0, 0.5]);
+    }
+
+    #[test]
+    fn repeated_roots_are_returned_once() {
+        assert_roots(&from_roots(&[0.7, 0.7]), -10.0..10.0, &[0.7]);
+        assert_roots(&from_roots(&[0.1, 0.1, 2.0]), -10.0..10.0, &[0.1, 2.0]);
+        assert_roots(
+            &from_roots(&[-0.7, -0.7, 0.3, 0.3]),
+            -10.0..10.0,
+            &[-0.7, 0.3],
+        );
+        assert_roots(
+            &from_roots(&[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]),
+            -10.0..10.0,
+            &[1.0 / 3.0],
+        );
+    }
+}
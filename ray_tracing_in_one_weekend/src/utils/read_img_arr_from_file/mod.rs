@@ -0,0 +1,90 @@
+pub mod pfm;
+pub mod png;
+pub mod ppm;
+
+use std::{fs, path::Path};
+
+use crate::scene::{framebuffer::Framebuffer, output_transform::TransferFunction};
+
+use self::{pfm::decode_pfm, png::decode_png, ppm::decode_ppm};
+
+use super::image_io_error::ImageIoError;
+
+/// Reads image data (rows of `[r, g, b]` pixels) from a specified ".ppm" or ".png" file `path`
+/// (both ASCII P3 and binary P6 ".ppm" formats are supported)
+///
+/// Returns an error if the path's extension is not supported,
+/// the file cannot be read or its contents are not a valid image
+pub fn read_img_arr_from_file(path: &Path) -> Result<Vec<Vec<[u8; 3]>>, ImageIoError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    if extension.eq_ignore_ascii_case("ppm") {
+        decode_ppm(&fs::read(path)?)
+    } else if extension.eq_ignore_ascii_case("png") {
+        decode_png(&fs::read(path)?)
+    } else {
+        Err(ImageIoError::UnsupportedFormat(extension.to_string()))
+    }
+}
+
+/// Reads the image of linear colors from a specified ".pfm", ".ppm" or ".png" file `path`
+///
+/// 8-bit colors of ".ppm" and ".png" files are expected to be sRGB encoded
+pub fn read_framebuffer_from_file(path: &Path) -> Result<Framebuffer, ImageIoError> {
+    read_framebuffer_from_file_with_transfer(path, &TransferFunction::Srgb)
+}
+
+/// Reads the image of linear colors from a specified ".pfm", ".ppm" or ".png" file `path`
+/// decoding 8-bit colors of ".ppm" and ".png" files by the `transfer_function`
+/// (".pfm" colors are linear already)
+pub fn read_framebuffer_from_file_with_transfer(
+    path: &Path,
+    transfer_function: &TransferFunction,
+) -> Result<Framebuffer, ImageIoError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    if extension.eq_ignore_ascii_case("pfm") {
+        decode_pfm(&fs::read(path)?)
+    } else if extension.eq_ignore_ascii_case("ppm") || extension.eq_ignore_ascii_case("png") {
+        let img_arr = read_img_arr_from_file(path)?;
+
+        Ok(Framebuffer::from_img_arr(&img_arr, transfer_function))
+    } else {
+        Err(ImageIoError::UnsupportedFormat(extension.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_extension_is_unsupported_format() {
+        assert!(matches!(
+            read_img_arr_from_file(Path::new("image.bmp")),
+            Err(ImageIoError::UnsupportedFormat(extension)) if extension == "bmp"
+        ));
+        assert!(matches!(
+            read_framebuffer_from_file(Path::new("image.bmp")),
+            Err(ImageIoError::UnsupportedFormat(extension)) if extension == "bmp"
+        ));
+    }
+
+    #[test]
+    fn malformed_contents_are_invalid_data() {
+        assert!(matches!(
+            decode_ppm(b"P5 1 1 255\n\x00"),
+            Err(ImageIoError::InvalidData(_))
+        ));
+        assert!(matches!(
+            decode_png(b"not a png"),
+            Err(ImageIoError::InvalidData(_))
+        ));
+    }
+}
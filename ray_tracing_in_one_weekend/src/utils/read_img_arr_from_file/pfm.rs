@@ -0,0 +1,123 @@
+use crate::{scene::framebuffer::Framebuffer, utils::image_io_error::ImageIoError, vector::Vector};
+
+/// Decodes color portable float map (PF) into the framebuffer of linear colors.
+///
+/// The sign of the header's scale defines the samples' byte order
+/// (negative for little-endian), its magnitude is ignored
+pub fn decode_pfm(data: &[u8]) -> Result<Framebuffer, ImageIoError> {
+    // the header consists of 3 lines: magic number, size and scale
+    let mut lines = data.splitn(4, |c| *c == b'\n');
+    let mut next_line = || -> Result<&str, ImageIoError> {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid_data("unexpected end of file"))?;
+
+        std::str::from_utf8(line)
+            .map(str::trim)
+            .map_err(|_| invalid_data("header is not a valid text"))
+    };
+
+    if next_line()? != "PF" {
+        return Err(invalid_data("expected PF magic number"));
+    }
+
+    let size = next_line()?
+        .split_ascii_whitespace()
+        .map(|x| x.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid_data("expected image width and height"))?;
+    let [img_width, img_height] = size[..] else {
+        return Err(invalid_data("expected image width and height"));
+    };
+    if img_width == 0 || img_height == 0 {
+        return Err(invalid_data("image should not be empty"));
+    }
+
+    let scale = next_line()?
+        .parse::<f32>()
+        .ok()
+        .filter(|scale| *scale != 0.0 && scale.is_finite())
+        .ok_or_else(|| invalid_data("expected non-zero scale"))?;
+    let is_little_endian = scale < 0.0;
+
+    let raster = lines.next().unwrap_or_default();
+    // the sizes are checked before the framebuffer is allocated
+    let samples_num = img_width
+        .checked_mul(img_height)
+        .and_then(|pixels_num| pixels_num.checked_mul(3))
+        .ok_or_else(|| invalid_data("image is too large"))?;
+    if samples_num
+        .checked_mul(4)
+        .map_or(true, |raster_len| raster.len() < raster_len)
+    {
+        return Err(invalid_data("unexpected end of raster"));
+    }
+
+    let samples = raster
+        .chunks_exact(4)
+        .take(samples_num)
+        .map(|bytes| {
+            let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+            if is_little_endian {
+                f32::from_le_bytes(bytes)
+            } else {
+                f32::from_be_bytes(bytes)
+            }
+        })
+        .collect::<Vec<f32>>();
+
+    // rows are stored from the bottom row to the top one
+    let mut framebuffer = Framebuffer::new(img_width, img_height);
+    for (i, row) in samples.chunks_exact(img_width * 3).enumerate() {
+        for (x, pixel) in row.chunks_exact(3).enumerate() {
+            framebuffer.set_pixel(
+                x,
+                img_height - 1 - i,
+                Vector::new(pixel[0] as f64, pixel[1] as f64, pixel[2] as f64),
+            );
+        }
+    }
+
+    Ok(framebuffer)
+}
+
+fn invalid_data(message: &str) -> ImageIoError {
+    ImageIoError::InvalidData(format!("invalid pfm: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_both_byte_orders() {
+        let mut little_endian = b"PF\n1 2\n-1.0\n".to_vec();
+        let mut big_endian = b"PF\n1 2\n1.0\n".to_vec();
+        // bottom row first
+        for sample in [0.25_f32, 0.5, 1.0, 2.0, 4.0, 8.0] {
+            little_endian.extend(sample.to_le_bytes());
+            big_endian.extend(sample.to_be_bytes());
+        }
+
+        for data in [little_endian, big_endian] {
+            let framebuffer = decode_pfm(&data).unwrap();
+
+            assert_eq!(framebuffer.get_pixel(0, 0).x(), 2.0);
+            assert_eq!(framebuffer.get_pixel(0, 1).z(), 1.0);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_raster() {
+        assert!(decode_pfm(b"PF\n1 1\n-1\n").is_err());
+        assert!(decode_pfm(b"PF\n1 1\n-1\n\x00\x00\x00\x00").is_err());
+        assert!(decode_pfm(b"PF\n1 1\n").is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_dimensions() {
+        assert!(decode_pfm(b"PF\n4611686018427387904 1\n-1\n").is_err());
+        assert!(decode_pfm(b"PF\n6148914691236517206 3\n-1\n").is_err());
+        assert!(decode_pfm(b"PF\n1000000 1000000\n-1\n").is_err());
+    }
+}
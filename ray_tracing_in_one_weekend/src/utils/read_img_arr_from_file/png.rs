@@ -0,0 +1,51 @@
+use png::{ColorType, Decoder, DecodingError, Transformations};
+
+use crate::utils::image_io_error::ImageIoError;
+
+/// Decodes portable network graphics (PNG) image into rows of `[r, g, b]` pixels
+///
+/// Images of any color type and bit depth are converted into 8-bit RGB,
+/// the alpha channel is dropped
+pub fn decode_png(data: &[u8]) -> Result<Vec<Vec<[u8; 3]>>, ImageIoError> {
+    let mut decoder = Decoder::new(data);
+    // palette and low bit depth images are expanded and 16-bit samples are stripped to 8 bits
+    decoder.set_transformations(Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info().map_err(decoding_error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(decoding_error)?;
+
+    let samples_per_pixel = match info.color_type {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        ColorType::Indexed => {
+            return Err(ImageIoError::InvalidData(
+                "invalid png: indexed colors should be expanded".to_string(),
+            ))
+        }
+    };
+
+    let img_arr = buffer[..info.buffer_size()]
+        .chunks_exact(info.line_size)
+        .map(|line| {
+            line.chunks_exact(samples_per_pixel)
+                .take(info.width as usize)
+                .map(|pixel| match samples_per_pixel {
+                    1 | 2 => [pixel[0]; 3],
+                    _ => [pixel[0], pixel[1], pixel[2]],
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(img_arr)
+}
+
+fn decoding_error(e: DecodingError) -> ImageIoError {
+    match e {
+        DecodingError::IoError(e) => ImageIoError::Io(e),
+        e => ImageIoError::InvalidData(format!("invalid png: {e}")),
+    }
+}
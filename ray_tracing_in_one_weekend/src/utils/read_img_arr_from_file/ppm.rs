@@ -0,0 +1,159 @@
+use crate::utils::image_io_error::ImageIoError;
+
+/// Decodes portable pixmap image either in ASCII (P3) or binary (P6) format
+/// into rows of `[r, g, b]` pixels.
+///
+/// Samples with maximal value other than 255 are rescaled into 0-255 range
+pub fn decode_ppm(data: &[u8]) -> Result<Vec<Vec<[u8; 3]>>, ImageIoError> {
+    let mut header = PpmHeaderReader { data, pos: 0 };
+
+    let is_binary = match header.next_token()? {
+        b"P3" => false,
+        b"P6" => true,
+        _ => return Err(invalid_data("expected P3 or P6 magic number")),
+    };
+    let img_width = header.next_number()?;
+    let img_height = header.next_number()?;
+    let max_value = header.next_number()?;
+
+    if img_width == 0 || img_height == 0 {
+        return Err(invalid_data("image should not be empty"));
+    }
+    if max_value == 0 || max_value > u16::MAX as usize {
+        return Err(invalid_data(
+            "maximal sample value should be in 1..=65535 range",
+        ));
+    }
+
+    let samples_num = img_width
+        .checked_mul(img_height)
+        .and_then(|pixels_num| pixels_num.checked_mul(3))
+        .ok_or_else(|| invalid_data("image is too large"))?;
+    let rescale = |x: usize| -> Result<u8, ImageIoError> {
+        if x > max_value {
+            return Err(invalid_data("sample value exceeds the maximal value"));
+        }
+
+        Ok(((x * 255 + max_value / 2) / max_value) as u8)
+    };
+
+    let samples = if is_binary {
+        // a single whitespace separates the header from the raster
+        let raster = data
+            .get(header.pos + 1..)
+            .ok_or_else(|| invalid_data("unexpected end of raster"))?;
+        let bytes_per_sample = if max_value > 255 { 2 } else { 1 };
+
+        if samples_num
+            .checked_mul(bytes_per_sample)
+            .map_or(true, |raster_len| raster.len() < raster_len)
+        {
+            return Err(invalid_data("unexpected end of raster"));
+        }
+
+        raster
+            .chunks_exact(bytes_per_sample)
+            .take(samples_num)
+            .map(|bytes| rescale(bytes.iter().fold(0, |acc, b| acc * 256 + *b as usize)))
+            .collect::<Result<Vec<u8>, ImageIoError>>()?
+    } else {
+        (0..samples_num)
+            .map(|_| header.next_number().and_then(rescale))
+            .collect::<Result<Vec<u8>, ImageIoError>>()?
+    };
+
+    Ok(samples
+        .chunks_exact(img_width * 3)
+        .map(|row| {
+            row.chunks_exact(3)
+                .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                .collect()
+        })
+        .collect())
+}
+
+fn invalid_data(message: &str) -> ImageIoError {
+    ImageIoError::InvalidData(format!("invalid ppm: {message}"))
+}
+
+/// Reads whitespace separated tokens skipping `#` comments
+struct PpmHeaderReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PpmHeaderReader<'a> {
+    fn next_token(&mut self) -> Result<&'a [u8], ImageIoError> {
+        loop {
+            match self.data.get(self.pos) {
+                Some(b'#') => {
+                    while self.data.get(self.pos).is_some_and(|c| *c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(_) => break,
+                None => return Err(invalid_data("unexpected end of file")),
+            }
+        }
+
+        let start = self.pos;
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|c| !c.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+
+        Ok(&self.data[start..self.pos])
+    }
+
+    fn next_number(&mut self) -> Result<usize, ImageIoError> {
+        let token = self.next_token()?;
+
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse::<usize>().ok())
+            .ok_or_else(|| invalid_data("expected a non-negative integer"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_ascii_and_binary() {
+        let expected = vec![vec![[255, 0, 0], [0, 128, 255]]];
+
+        assert_eq!(
+            decode_ppm(b"P3\n# comment\n2 1\n255\n255 0 0 0 128 255\n").unwrap(),
+            expected
+        );
+        assert_eq!(
+            decode_ppm(b"P6 2 1 255\n\xff\x00\x00\x00\x80\xff").unwrap(),
+            expected
+        );
+        // samples are rescaled from the maximal value
+        assert_eq!(
+            decode_ppm(b"P3 1 1 510 510 0 256").unwrap(),
+            vec![vec![[255, 0, 128]]]
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_raster() {
+        assert!(decode_ppm(b"P6 1 1 255").is_err());
+        assert!(decode_ppm(b"P6 1 1 255\n").is_err());
+        assert!(decode_ppm(b"P6 1 1 255\n\x00\x00").is_err());
+        assert!(decode_ppm(b"P3 1 1 255 0 0").is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_dimensions() {
+        assert!(decode_ppm(b"P6 6148914691236517206 3 255\n\x00").is_err());
+        assert!(decode_ppm(b"P6 18446744073709551615 18446744073709551615 65535\n").is_err());
+        assert!(decode_ppm(b"P3 6148914691236517206 3 255 0").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{self, Write};
+
+use crate::{scene::framebuffer::Framebuffer, vector::Vector};
+
+use super::HdrImageWriter;
+
+/// OpenEXR magic number
+const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+/// File format version 2 with no flags set (single-part scanline image)
+const VERSION: [u8; 4] = [2, 0, 0, 0];
+/// Channel pixel type of 32-bit float samples
+const PIXEL_TYPE_FLOAT: i32 = 2;
+
+/// Writes single-part scanline OpenEXR images without compression:
+/// each scanline is a separate block with 32-bit float B, G and R channels
+pub struct ExrWriter;
+
+/// Appends the header attribute `name` of type `attr_type` with the given `value`
+fn push_attribute(header: &mut Vec<u8>, name: &str, attr_type: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(attr_type.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+/// Returns `box2i` attribute value of the window from (0, 0) to (`width` - 1, `height` - 1)
+fn get_window(width: usize, height: usize) -> Vec<u8> {
+    [0, 0, width as i32 - 1, height as i32 - 1]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect()
+}
+
+impl HdrImageWriter for ExrWriter {
+    fn write(&self, writer: &mut dyn Write, framebuffer: &Framebuffer) -> io::Result<()> {
+        let width = framebuffer.width();
+        let height = framebuffer.height();
+        if width > i32::MAX as usize || height > i32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "image is too large for OpenEXR",
+            ));
+        }
+
+        // channels are stored in alphabetical order
+        let mut channels = vec![];
+        for name in ["B", "G", "R"] {
+            channels.extend_from_slice(name.as_bytes());
+            channels.push(0);
+            channels.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+            // pLinear flag and reserved bytes
+            channels.extend_from_slice(&[0; 4]);
+            // x and y sampling
+            channels.extend_from_slice(&1_i32.to_le_bytes());
+            channels.extend_from_slice(&1_i32.to_le_bytes());
+        }
+        channels.push(0);
+
+        let mut header = vec![];
+        header.extend_from_slice(&MAGIC);
+        header.extend_from_slice(&VERSION);
+        push_attribute(&mut header, "channels", "chlist", &channels);
+        // no compression
+        push_attribute(&mut header, "compression", "compression", &[0]);
+        push_attribute(
+            &mut header,
+            "dataWindow",
+            "box2i",
+            &get_window(width, height),
+        );
+        push_attribute(
+            &mut header,
+            "displayWindow",
+            "box2i",
+            &get_window(width, height),
+        );
+        // increasing y
+        push_attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+        push_attribute(
+            &mut header,
+            "pixelAspectRatio",
+            "float",
+            &1.0_f32.to_le_bytes(),
+        );
+        push_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+        push_attribute(
+            &mut header,
+            "screenWindowWidth",
+            "float",
+            &1.0_f32.to_le_bytes(),
+        );
+        header.push(0);
+
+        // the offset table holds the file position of each scanline block
+        // (the block consists of y coordinate, data size and the channels' samples)
+        let data_size = width * 3 * 4;
+        let block_size = 4 + 4 + data_size;
+        let first_block_offset = header.len() + height * 8;
+
+        writer.write_all(&header)?;
+        for y in 0..height {
+            writer.write_all(&((first_block_offset + y * block_size) as u64).to_le_bytes())?;
+        }
+
+        for (y, row) in framebuffer.rows().enumerate() {
+            let mut block = Vec::with_capacity(block_size);
+            block.extend_from_slice(&(y as i32).to_le_bytes());
+            block.extend_from_slice(&(data_size as u32).to_le_bytes());
+
+            for get_channel in [Vector::z, Vector::y, Vector::x] {
+                for color in row {
+                    block.extend_from_slice(&(get_channel(color) as f32).to_le_bytes());
+                }
+            }
+
+            writer.write_all(&block)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,46 +1,245 @@
-use std::{ffi::OsStr, fs, io::Write};
+pub mod exr;
+pub mod pfm;
+pub mod png;
+pub mod ppm;
 
-/// Writes image data `img_arr` to a specified file `path`
-/// # Panics
-/// Panics if the specified file path does not end with a ".ppm" extension
-/// or the file with provided path cannot be created or written to
-pub fn write_img_arr_to_file(path: &std::path::Path, img_arr: Vec<Vec<[u8; 3]>>) {
-    assert_eq!(
-        path.extension().unwrap(),
-        "ppm",
-        "Expected .ppm file, got {}",
-        path.extension().and_then(OsStr::to_str).unwrap()
-    );
+use std::{
+    ffi::OsStr,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::scene::{framebuffer::Framebuffer, output_transform::OutputTransform};
+
+use self::{exr::ExrWriter, pfm::PfmWriter, png::PngWriter, ppm::PpmWriter};
 
-    let img_height = img_arr.len();
-    let img_width = img_arr[0].len();
+use super::image_io_error::ImageIoError;
 
-    fs::create_dir_all(path.parent().unwrap()).unwrap();
+/// Encodes image data into a specific image file format
+pub trait ImageWriter {
+    /// Writes image data `img_arr` (rows of `[r, g, b]` pixels) to `writer`
+    ///
+    /// The image is expected to be non-empty with rows of the same length
+    fn write(&self, writer: &mut dyn Write, img_arr: &[Vec<[u8; 3]>]) -> io::Result<()>;
+}
+
+/// Encodes high dynamic range images into a specific image file format
+pub trait HdrImageWriter {
+    /// Writes linear colors of the non-empty `framebuffer` to `writer`
+    fn write(&self, writer: &mut dyn Write, framebuffer: &Framebuffer) -> io::Result<()>;
+}
 
-    match fs::OpenOptions::new()
+/// Supported image file formats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// ASCII portable pixmap (P3)
+    Ppm,
+    /// Binary portable pixmap (P6)
+    PpmBinary,
+    /// Portable network graphics
+    Png,
+    /// Portable float map (high dynamic range)
+    Pfm,
+    /// Uncompressed OpenEXR (high dynamic range)
+    Exr,
+}
+
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 5] = [
+        ImageFormat::Ppm,
+        ImageFormat::PpmBinary,
+        ImageFormat::Png,
+        ImageFormat::Pfm,
+        ImageFormat::Exr,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::PpmBinary => "ppm-binary",
+            ImageFormat::Png => "png",
+            ImageFormat::Pfm => "pfm",
+            ImageFormat::Exr => "exr",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm | ImageFormat::PpmBinary => "ppm",
+            ImageFormat::Png => "png",
+            ImageFormat::Pfm => "pfm",
+            ImageFormat::Exr => "exr",
+        }
+    }
+
+    /// Whether the format stores linear float colors instead of 8-bit ones
+    pub fn is_hdr(&self) -> bool {
+        matches!(self, ImageFormat::Pfm | ImageFormat::Exr)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Detects the format by the path's extension
+    /// (the first format with the extension is chosen, e.g. ASCII ppm for ".ppm")
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension().and_then(OsStr::to_str)?;
+
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+
+    /// Returns the writer of 8-bit images (`None` for high dynamic range formats)
+    pub fn writer(&self) -> Option<Box<dyn ImageWriter>> {
+        match self {
+            ImageFormat::Ppm => Some(Box::new(PpmWriter::new(false))),
+            ImageFormat::PpmBinary => Some(Box::new(PpmWriter::new(true))),
+            ImageFormat::Png => Some(Box::new(PngWriter)),
+            ImageFormat::Pfm | ImageFormat::Exr => None,
+        }
+    }
+
+    /// Returns the writer of high dynamic range images (`None` for 8-bit formats)
+    pub fn hdr_writer(&self) -> Option<Box<dyn HdrImageWriter>> {
+        match self {
+            ImageFormat::Pfm => Some(Box::new(PfmWriter)),
+            ImageFormat::Exr => Some(Box::new(ExrWriter)),
+            _ => None,
+        }
+    }
+}
+
+/// Detects the format by the path's extension
+fn get_format_from_path(path: &Path) -> Result<ImageFormat, ImageIoError> {
+    ImageFormat::from_path(path).ok_or_else(|| {
+        ImageIoError::UnsupportedFormat(
+            path.extension()
+                .and_then(OsStr::to_str)
+                .unwrap_or_default()
+                .to_string(),
+        )
+    })
+}
+
+/// Creates (or truncates) the file with provided `path` creating the parent directories if needed
+fn create_img_file(path: &Path) -> Result<BufWriter<File>, ImageIoError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let img_file = fs::OpenOptions::new()
         .write(true)
         .truncate(true)
         .create(true)
-        .open(path)
+        .open(path)?;
+
+    Ok(BufWriter::new(img_file))
+}
+
+/// Writes image data `img_arr` to a specified file `path`
+/// in the format defined by the path's extension
+///
+/// Returns an error if the path's extension is not of an 8-bit format (".ppm" or ".png",
+/// ".pfm" and ".exr" files are written by `write_framebuffer_to_file`)
+/// or the image cannot be written (see `write_img_arr_to_file_with_format`)
+pub fn write_img_arr_to_file(
+    path: &std::path::Path,
+    img_arr: Vec<Vec<[u8; 3]>>,
+) -> Result<(), ImageIoError> {
+    write_img_arr_to_file_with_format(path, img_arr, get_format_from_path(path)?)
+}
+
+/// Writes image data `img_arr` to a specified file `path` in the given `format`
+/// creating the parent directories if needed
+///
+/// Returns an error if the format is a high dynamic range one,
+/// the image is empty, its rows have different lengths
+/// or the file with provided path cannot be created or written to
+pub fn write_img_arr_to_file_with_format(
+    path: &std::path::Path,
+    img_arr: Vec<Vec<[u8; 3]>>,
+    format: ImageFormat,
+) -> Result<(), ImageIoError> {
+    let img_writer = format
+        .writer()
+        .ok_or_else(|| ImageIoError::UnsupportedFormat(format.name().to_string()))?;
+
+    let img_width = img_arr.first().map_or(0, Vec::len);
+    if img_width == 0 {
+        return Err(ImageIoError::EmptyImage);
+    }
+
+    if let Some((row, col)) = img_arr
+        .iter()
+        .enumerate()
+        .find(|(_, col)| col.len() != img_width)
     {
-        Err(e) => panic!("Couldn't open file: {e:?}"),
-        Ok(mut img_file) => {
-            // write default ppm headers
-            img_file.write_all(b"P3\n").unwrap();
-            img_file
-                .write_all(format!("{img_width} {img_height}\n").as_bytes())
-                .unwrap();
-            img_file.write_all(("255\n").as_bytes()).unwrap();
-
-            // write image
-            for col in img_arr {
-                for pixel in col {
-                    img_file
-                        .write_all(pixel.map(|x| format!("{x}")).join(" ").as_bytes())
-                        .unwrap();
-                    img_file.write_all(b" ").unwrap();
-                }
-            }
-        }
+        return Err(ImageIoError::RaggedRows {
+            row,
+            expected_width: img_width,
+            actual_width: col.len(),
+        });
+    }
+
+    let mut writer = create_img_file(path)?;
+    img_writer.write(&mut writer, &img_arr)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Writes the rendered `framebuffer` to a specified file `path`
+/// in the format defined by the path's extension
+/// (see `write_framebuffer_to_file_with_format`)
+pub fn write_framebuffer_to_file(
+    path: &Path,
+    framebuffer: &Framebuffer,
+    output_transform: &OutputTransform,
+) -> Result<(), ImageIoError> {
+    write_framebuffer_to_file_with_format(
+        path,
+        framebuffer,
+        output_transform,
+        get_format_from_path(path)?,
+    )
+}
+
+/// Writes the rendered `framebuffer` to a specified file `path` in the given `format`
+///
+/// High dynamic range formats keep the linear colors as is,
+/// 8-bit formats store the colors converted with `output_transform`
+///
+/// Returns an error if the image is empty
+/// or the file with provided path cannot be created or written to
+pub fn write_framebuffer_to_file_with_format(
+    path: &Path,
+    framebuffer: &Framebuffer,
+    output_transform: &OutputTransform,
+    format: ImageFormat,
+) -> Result<(), ImageIoError> {
+    let Some(hdr_writer) = format.hdr_writer() else {
+        return write_img_arr_to_file_with_format(
+            path,
+            framebuffer.to_img_arr(output_transform),
+            format,
+        );
+    };
+
+    if framebuffer.is_empty() {
+        return Err(ImageIoError::EmptyImage);
     }
+
+    let mut writer = create_img_file(path)?;
+    hdr_writer.write(&mut writer, framebuffer)?;
+    writer.flush()?;
+
+    Ok(())
 }
@@ -0,0 +1,31 @@
+use std::io::{self, Write};
+
+use crate::scene::framebuffer::Framebuffer;
+
+use super::HdrImageWriter;
+
+/// Writes color portable float maps (PF): little-endian 32-bit float samples
+/// stored from the bottom row to the top one
+pub struct PfmWriter;
+
+impl HdrImageWriter for PfmWriter {
+    fn write(&self, writer: &mut dyn Write, framebuffer: &Framebuffer) -> io::Result<()> {
+        // the negative scale marks little-endian samples
+        writer.write_all(b"PF\n")?;
+        writer
+            .write_all(format!("{} {}\n", framebuffer.width(), framebuffer.height()).as_bytes())?;
+        writer.write_all(b"-1.0\n")?;
+
+        for row in framebuffer.rows().rev() {
+            let bytes = row
+                .iter()
+                .flat_map(|color| [color.x(), color.y(), color.z()])
+                .flat_map(|x| (x as f32).to_le_bytes())
+                .collect::<Vec<u8>>();
+
+            writer.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+}
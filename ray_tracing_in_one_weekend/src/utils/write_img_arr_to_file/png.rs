@@ -0,0 +1,32 @@
+use std::io::{self, Write};
+
+use png::{BitDepth, ColorType, Encoder};
+
+use super::ImageWriter;
+
+/// Writes 8-bit RGB portable network graphics (PNG) images
+pub struct PngWriter;
+
+impl ImageWriter for PngWriter {
+    fn write(&self, writer: &mut dyn Write, img_arr: &[Vec<[u8; 3]>]) -> io::Result<()> {
+        let img_height = img_arr.len();
+        let img_width = img_arr[0].len();
+
+        let mut encoder = Encoder::new(writer, img_width as u32, img_height as u32);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+
+        let data = img_arr
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect::<Vec<u8>>();
+
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&data)?;
+        png_writer.finish()?;
+
+        Ok(())
+    }
+}
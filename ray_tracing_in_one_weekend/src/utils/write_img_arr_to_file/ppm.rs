@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use super::ImageWriter;
+
+/// Writes portable pixmap images either as ASCII (P3) or binary (P6)
+pub struct PpmWriter {
+    is_binary: bool,
+}
+
+impl PpmWriter {
+    pub fn new(is_binary: bool) -> Self
+    where
+        Self: Sized,
+    {
+        Self { is_binary }
+    }
+}
+
+impl ImageWriter for PpmWriter {
+    fn write(&self, writer: &mut dyn Write, img_arr: &[Vec<[u8; 3]>]) -> io::Result<()> {
+        let img_height = img_arr.len();
+        let img_width = img_arr[0].len();
+
+        // write default ppm headers
+        writer.write_all(if self.is_binary { b"P6\n" } else { b"P3\n" })?;
+        writer.write_all(format!("{img_width} {img_height}\n").as_bytes())?;
+        writer.write_all(("255\n").as_bytes())?;
+
+        // write image
+        for col in img_arr {
+            if self.is_binary {
+                writer.write_all(&col.concat())?;
+                continue;
+            }
+
+            for pixel in col {
+                writer.write_all(pixel.map(|x| format!("{x}")).join(" ").as_bytes())?;
+                writer.write_all(b" ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use std::ops::Mul;
+
+use super::Vector;
+
+/// 3x3 matrix of linear transforms (stored row by row)
+///
+/// Vectors are columns multiplied on the right: `M * v`,
+/// so `A * B` applies `B` first
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat3 {
+    rows: [[f64; 3]; 3],
+}
+
+impl Default for Mat3 {
+    /// Identity matrix
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mat3 {
+    pub fn new(rows: [[f64; 3]; 3]) -> Self
+    where
+        Self: Sized,
+    {
+        Self { rows }
+    }
+
+    pub fn identity() -> Self {
+        Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates the matrix with the given columns (the images of the x, y and z axes)
+    pub fn from_columns(x: &Vector, y: &Vector, z: &Vector) -> Self {
+        Self::new([
+            [x.x(), y.x(), z.x()],
+            [x.y(), y.y(), z.y()],
+            [x.z(), y.z(), z.z()],
+        ])
+    }
+
+    /// Non-uniform scaling along the axes by the given factors
+    pub fn scaling(factors: &Vector) -> Self {
+        Self::new([
+            [factors.x(), 0.0, 0.0],
+            [0.0, factors.y(), 0.0],
+            [0.0, 0.0, factors.z()],
+        ])
+    }
+
+    /// Counterclockwise rotation around the non-zero `axis` by the `angle` (in radians)
+    /// when looking from the axis' end
+    pub fn rotation(axis: &Vector, angle: f64) -> Self {
+        // Rodrigues' rotation formula
+        let axis = axis.normalize();
+        let (x, y, z) = (axis.x(), axis.y(), axis.z());
+        let (sin, cos) = angle.sin_cos();
+        let k = 1.0 - cos;
+
+        Self::new([
+            [cos + x * x * k, x * y * k - z * sin, x * z * k + y * sin],
+            [y * x * k + z * sin, cos + y * y * k, y * z * k - x * sin],
+            [z * x * k - y * sin, z * y * k + x * sin, cos + z * z * k],
+        ])
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.rows[row][column]
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = self.rows[j][i];
+            }
+        }
+
+        Self::new(rows)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let [a, b, c] = self.rows;
+
+        Vector::from(a).dot(&Vector::from(b).cross(&Vector::from(c)))
+    }
+
+    /// Matrix of the cofactors: the inverse's transpose scaled by the determinant
+    pub fn cofactor(&self) -> Self {
+        // each row of the cofactor matrix is the cross product of the other two rows
+        let [a, b, c] = self.rows.map(Vector::from);
+
+        Self::new([b.cross(&c), c.cross(&a), a.cross(&b)].map(|row| [row.x(), row.y(), row.z()]))
+    }
+
+    /// Returns the inverse matrix or `None` if the matrix is singular
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+
+        let adjugate = self.cofactor().transpose();
+
+        Some(Self::new(
+            adjugate.rows.map(|row| row.map(|x| x / determinant)),
+        ))
+    }
+
+    /// Matrix transforming the normal vectors of the surfaces transformed by this matrix
+    /// (the inverse's transpose up to positive scale, so the transformed normals must be normalized)
+    ///
+    /// Unlike the inverse it exists for singular matrices too
+    pub fn normal_matrix(&self) -> Self {
+        let sign = 1.0_f64.copysign(self.determinant());
+
+        Self::new(self.cofactor().rows.map(|row| row.map(|x| x * sign)))
+    }
+
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        let [x, y, z] = self.rows.map(|row| Vector::from(row).dot(vector));
+
+        Vector::new(x, y, z)
+    }
+
+    /// Transforms the normal vector (unit length) of the surface transformed by this matrix
+    pub fn transform_normal(&self, normal_vector: &Vector) -> Vector {
+        self.normal_matrix()
+            .transform_vector(normal_vector)
+            .normalize()
+    }
+}
+
+impl Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, rhs: Mat3) -> Self::Output {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = (0..3).map(|k| self.rows[i][k] * rhs.rows[k][j]).sum();
+            }
+        }
+
+        Mat3::new(rows)
+    }
+}
+
+impl Mul<Vector> for Mat3 {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Self::Output {
+        self.transform_vector(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn assert_close(lhs: &Mat3, rhs: &Mat3) {
+        for row in 0..3 {
+            for column in 0..3 {
+                assert!(
+                    (lhs.get(row, column) - rhs.get(row, column)).abs() < 1e-12,
+                    "{lhs:?} != {rhs:?}"
+                );
+            }
+        }
+    }
+
+    fn sample_matrix() -> Mat3 {
+        Mat3::new([[2.0, -1.0, 0.5], [0.0, 3.0, 1.0], [1.0, 4.0, -2.0]])
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let matrix = sample_matrix();
+        let inverse = matrix.inverse().unwrap();
+
+        assert_close(&(inverse * matrix), &Mat3::identity());
+        assert_close(&(matrix * inverse), &Mat3::identity());
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let singular = Mat3::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 5.0]]);
+
+        assert!(singular.inverse().is_none());
+        assert!(Mat3::scaling(&Vector::new(1.0, 0.0, 2.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let matrix = sample_matrix();
+        let transposed = matrix.transpose();
+
+        for row in 0..3 {
+            for column in 0..3 {
+                assert_eq!(transposed.get(row, column), matrix.get(column, row));
+            }
+        }
+        assert_close(&transposed.transpose(), &matrix);
+    }
+
+    #[test]
+    fn rotation_is_counterclockwise() {
+        let rotation = Mat3::rotation(&Vector::new(0.0, 0.0, 2.0), PI / 2.0);
+        let rotated = rotation.transform_vector(&Vector::new(1.0, 0.0, 0.0));
+
+        assert!((rotated - Vector::new(0.0, 1.0, 0.0)).sq_norm() < 1e-24);
+        assert_close(&rotation.inverse().unwrap(), &rotation.transpose());
+    }
+
+    #[test]
+    fn transformed_normal_stays_perpendicular() {
+        let matrix = Mat3::rotation(&Vector::new(1.0, 1.0, 0.0), 0.7)
+            * Mat3::scaling(&Vector::new(3.0, 0.5, 1.0));
+        // the plane x + y + z = 0 is spanned by two tangents
+        let normal = Vector::new(1.0, 1.0, 1.0).normalize();
+        let tangents = [Vector::new(1.0, -1.0, 0.0), Vector::new(0.0, 1.0, -1.0)];
+
+        let transformed = matrix.transform_normal(&normal);
+
+        assert!((transformed.sq_norm() - 1.0).abs() < 1e-12);
+        for tangent in tangents {
+            assert!(transformed.dot(&matrix.transform_vector(&tangent)).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::ops::Mul;
+
+use super::{mat3::Mat3, Vector};
+
+/// 4x4 matrix of homogeneous transforms (stored row by row)
+///
+/// Points and vectors are columns multiplied on the right: `M * p`,
+/// so `A * B` applies `B` first
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    rows: [[f64; 4]; 4],
+}
+
+impl Default for Mat4 {
+    /// Identity matrix
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mat4 {
+    pub fn new(rows: [[f64; 4]; 4]) -> Self
+    where
+        Self: Sized,
+    {
+        Self { rows }
+    }
+
+    pub fn identity() -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn translation(offset: &Vector) -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, offset.x()],
+            [0.0, 1.0, 0.0, offset.y()],
+            [0.0, 0.0, 1.0, offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Linear transform of the matrix with no translation
+    pub fn from_mat3(matrix: &Mat3) -> Self {
+        let mut rows = Self::identity().rows;
+        for (i, row) in rows.iter_mut().take(3).enumerate() {
+            for (j, x) in row.iter_mut().take(3).enumerate() {
+                *x = matrix.get(i, j);
+            }
+        }
+
+        Self::new(rows)
+    }
+
+    /// Linear part of the transform (the upper-left 3x3 block)
+    pub fn to_mat3(&self) -> Mat3 {
+        Mat3::new([0, 1, 2].map(|i| [0, 1, 2].map(|j| self.rows[i][j])))
+    }
+
+    /// Non-uniform scaling along the axes by the given factors
+    pub fn scaling(factors: &Vector) -> Self {
+        Self::from_mat3(&Mat3::scaling(factors))
+    }
+
+    /// Counterclockwise rotation around the non-zero `axis` by the `angle` (in radians)
+    /// when looking from the axis' end
+    pub fn rotation(axis: &Vector, angle: f64) -> Self {
+        Self::from_mat3(&Mat3::rotation(axis, angle))
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.rows[row][column]
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = self.rows[j][i];
+            }
+        }
+
+        Self::new(rows)
+    }
+
+    /// Returns the inverse matrix or `None` if the matrix is singular
+    pub fn inverse(&self) -> Option<Self> {
+        // Gauss-Jordan elimination with partial pivoting
+        // turning the matrix into the identity and the identity into the inverse
+        let mut lhs = self.rows;
+        let mut rhs = Self::identity().rows;
+
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|i, j| lhs[*i][column].abs().total_cmp(&lhs[*j][column].abs()))
+                .unwrap_or(column);
+            if lhs[pivot][column] == 0.0 {
+                return None;
+            }
+            lhs.swap(column, pivot);
+            rhs.swap(column, pivot);
+
+            let scale = 1.0 / lhs[column][column];
+            for j in 0..4 {
+                lhs[column][j] *= scale;
+                rhs[column][j] *= scale;
+            }
+
+            for i in (0..4).filter(|i| *i != column) {
+                let factor = lhs[i][column];
+                for j in 0..4 {
+                    lhs[i][j] -= factor * lhs[column][j];
+                    rhs[i][j] -= factor * rhs[column][j];
+                }
+            }
+        }
+
+        Some(Self::new(rhs))
+    }
+
+    /// Transforms the point (the translation applies to it)
+    pub fn transform_point(&self, point: &Vector) -> Vector {
+        let [x, y, z, w] = self.mul_column([point.x(), point.y(), point.z(), 1.0]);
+
+        if w == 1.0 || w == 0.0 {
+            Vector::new(x, y, z)
+        } else {
+            Vector::new(x / w, y / w, z / w)
+        }
+    }
+
+    /// Transforms the direction vector (the translation does not apply to it)
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        let [x, y, z, _] = self.mul_column([vector.x(), vector.y(), vector.z(), 0.0]);
+
+        Vector::new(x, y, z)
+    }
+
+    /// Transforms the normal vector (unit length) of the surface transformed by the affine matrix
+    pub fn transform_normal(&self, normal_vector: &Vector) -> Vector {
+        self.to_mat3().transform_normal(normal_vector)
+    }
+
+    fn mul_column(&self, column: [f64; 4]) -> [f64; 4] {
+        self.rows
+            .map(|row| row.iter().zip(column).map(|(a, b)| a * b).sum())
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Self::Output {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = (0..4).map(|k| self.rows[i][k] * rhs.rows[k][j]).sum();
+            }
+        }
+
+        Mat4::new(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(lhs: &Mat4, rhs: &Mat4) {
+        for row in 0..4 {
+            for column in 0..4 {
+                assert!(
+                    (lhs.get(row, column) - rhs.get(row, column)).abs() < 1e-12,
+                    "{lhs:?} != {rhs:?}"
+                );
+            }
+        }
+    }
+
+    fn assert_vectors_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-24, "{lhs:?} != {rhs:?}");
+    }
+
+    fn sample_transform() -> Mat4 {
+        Mat4::translation(&Vector::new(1.0, -2.0, 3.0))
+            * Mat4::rotation(&Vector::new(0.0, 1.0, 1.0), 0.4)
+            * Mat4::scaling(&Vector::new(2.0, 0.5, 3.0))
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        // a general matrix needs pivoting (its first diagonal element is zero)
+        for matrix in [
+            sample_transform(),
+            Mat4::new([
+                [0.0, 2.0, -1.0, 1.0],
+                [1.0, 1.0, 0.0, 2.0],
+                [3.0, -1.0, 2.0, 0.0],
+                [1.0, 0.0, 1.0, 1.0],
+            ]),
+        ] {
+            let inverse = matrix.inverse().unwrap();
+
+            assert_close(&(inverse * matrix), &Mat4::identity());
+            assert_close(&(matrix * inverse), &Mat4::identity());
+        }
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let singular = Mat4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [2.0, 4.0, 6.0, 8.0],
+            [1.0, 0.0, 0.0, 1.0],
+        ]);
+
+        assert!(singular.inverse().is_none());
+        assert!(Mat4::scaling(&Vector::new(1.0, 1.0, 0.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let matrix = sample_transform();
+        let transposed = matrix.transpose();
+
+        for row in 0..4 {
+            for column in 0..4 {
+                assert_eq!(transposed.get(row, column), matrix.get(column, row));
+            }
+        }
+    }
+
+    #[test]
+    fn translation_moves_points_only() {
+        let translation = Mat4::translation(&Vector::new(1.0, -2.0, 3.0));
+        let v = Vector::new(4.0, 5.0, 6.0);
+
+        assert_vectors_close(translation.transform_point(&v), Vector::new(5.0, 3.0, 9.0));
+        assert_vectors_close(translation.transform_vector(&v), v);
+        assert_vectors_close(
+            translation.transform_normal(&Vector::new(0.0, 1.0, 0.0)),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn non_uniform_scaling() {
+        let scaling = Mat4::scaling(&Vector::new(2.0, 1.0, 0.5));
+
+        assert_vectors_close(
+            scaling.transform_point(&Vector::new(1.0, 1.0, 1.0)),
+            Vector::new(2.0, 1.0, 0.5),
+        );
+        assert_vectors_close(
+            scaling.transform_vector(&Vector::new(1.0, -1.0, 4.0)),
+            Vector::new(2.0, -1.0, 2.0),
+        );
+    }
+
+    #[test]
+    fn transformed_normal_stays_perpendicular() {
+        let transform = sample_transform();
+        // the plane x + 2y - z = 0 is spanned by two tangents
+        let normal = Vector::new(1.0, 2.0, -1.0).normalize();
+        let tangents = [Vector::new(2.0, -1.0, 0.0), Vector::new(1.0, 0.0, 1.0)];
+
+        let transformed = transform.transform_normal(&normal);
+
+        assert!((transformed.sq_norm() - 1.0).abs() < 1e-12);
+        for tangent in tangents {
+            // tangents are differences of the plane's points, translation cancels out
+            let transformed_tangent =
+                transform.transform_point(&tangent) - transform.transform_point(&Vector::default());
+
+            assert!(transformed.dot(&transformed_tangent).abs() < 1e-12);
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use rand::Rng;
-use std::{
-    f64::consts::PI,
-    ops::{Add, Div, Mul, Sub},
-};
+pub mod mat3;
+pub mod mat4;
+pub mod onb;
+pub mod quat;
+pub mod sampling;
+
+use rand::{Rng, RngCore};
+use std::ops::{Add, AddAssign, Div, Index, Mul, Neg, Sub};
 
 #[derive(Default, Clone, Copy, Debug)]
 pub struct Vector {
@@ -23,9 +26,17 @@ impl Vector {
         self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
     }
 
+    pub fn cross(&self, rhs: &Self) -> Self {
+        Vector::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
     /// Squared vector norm
     pub fn sq_norm(&self) -> f64 {
-        self.dot(&self)
+        self.dot(self)
     }
 
     pub fn normalize(&self) -> Self {
@@ -52,23 +63,41 @@ impl Vector {
         )
     }
 
-    /// Generates a random unit length vector
-    /// inside hemisphere defined by the given main normal
-    /// (default main normal is (0, 0, 1))
-    pub fn gen_rand_inside_hemisphere(n: Option<Vector>) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Linear interpolation: `self` at `t = 0` and `rhs` at `t = 1`
+    pub fn lerp(&self, rhs: &Vector, t: f64) -> Self {
+        self * (1.0 - t) + rhs * t
+    }
+
+    /// Reflects the vector about the given unit length normal vector
+    pub fn reflect(&self, n: &Vector) -> Self {
+        self - &(n * (2.0 * self.dot(n)))
+    }
 
-        let phi = rng.gen_range(-PI / 2.0..PI / 2.0);
-        let theta = rng.gen_range(0.0..2.0 * PI);
+    /// Refracts the unit length vector through a surface with the given unit length normal vector
+    /// (pointing against the vector) by Snell's law
+    ///
+    /// `eta_ratio` is the ratio of the refraction indices `eta_incident / eta_transmitted`
+    pub fn refract(&self, n: &Vector, eta_ratio: f64) -> Self {
+        let cos_theta = (-self.dot(n)).min(1.0);
+        let r_out_perp = (self + &(n * cos_theta)) * eta_ratio;
+        let r_out_parallel = n * (-(1.0 - r_out_perp.sq_norm()).abs().sqrt());
 
-        let mut random_vector =
-            Vector::new(phi.sin() * theta.cos(), phi.sin() * theta.sin(), phi.cos());
+        r_out_perp + r_out_parallel
+    }
 
-        if let Some(normal_vector) = n {
-            random_vector = random_vector + normal_vector - Vector::new(0.0, 0.0, 1.0);
+    /// Generates a random vector inside the unit sphere centered at (0, 0, 0)
+    pub fn gen_rand_inside_unit_sphere(rng: &mut dyn RngCore) -> Self {
+        loop {
+            let random_vector = Vector::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            );
+
+            if random_vector.sq_norm() < 1.0 {
+                return random_vector;
+            }
         }
-
-        random_vector
     }
 }
 
@@ -152,6 +181,15 @@ impl<'a> Add<&'a Vector> for &'a Vector {
     }
 }
 
+/// Component-wise multiplication
+impl Mul<Vector> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+    }
+}
+
 impl Sub<Vector> for Vector {
     type Output = Vector;
 
@@ -168,6 +206,48 @@ impl<'a> Sub<&'a Vector> for &'a Vector {
     }
 }
 
+impl AddAssign<Vector> for Vector {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Neg for &Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Coordinate by index: 0 for x, 1 for y, 2 for z
+impl Index<usize> for Vector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vector index should be in 0..3 range, got {index}"),
+        }
+    }
+}
+
+impl From<[f64; 3]> for Vector {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Vector::new(x, y, z)
+    }
+}
+
 /// Ray that is defined with origin point and unit length direction vector:
 /// `_r_(t) = _origin_ + t * _direction_`
 ///
@@ -227,3 +307,112 @@ impl From<Ray> for Vector {
         ray.to_vector(1.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(lhs: Vector, rhs: Vector) {
+        assert!((lhs - rhs).sq_norm() < 1e-18, "{lhs:?} != {rhs:?}");
+    }
+
+    #[test]
+    fn cross_is_right_handed() {
+        let (x, y, z) = (
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        );
+
+        assert_close(x.cross(&y), z);
+        assert_close(y.cross(&z), x);
+        assert_close(z.cross(&x), y);
+        assert_close(y.cross(&x), -z);
+        assert_close(
+            Vector::new(1.0, 2.0, 3.0).cross(&Vector::new(4.0, 5.0, 6.0)),
+            Vector::new(-3.0, 6.0, -3.0),
+        );
+    }
+
+    #[test]
+    fn reflect_flips_normal_component() {
+        let n = Vector::new(0.0, 1.0, 0.0);
+
+        assert_close(
+            Vector::new(1.0, -2.0, 3.0).reflect(&n),
+            Vector::new(1.0, 2.0, 3.0),
+        );
+    }
+
+    #[test]
+    fn refract_follows_snells_law() {
+        let n = Vector::new(0.0, 0.0, 1.0);
+        let eta_ratio = 1.0 / 1.5;
+        let sin_in = 0.5_f64;
+        let incident = Vector::new(sin_in, 0.0, -(1.0 - sin_in * sin_in).sqrt());
+
+        let refracted = incident.refract(&n, eta_ratio);
+
+        assert!((refracted.sq_norm() - 1.0).abs() < 1e-12);
+        assert!((refracted.x() - sin_in * eta_ratio).abs() < 1e-12);
+        assert!(refracted.y().abs() < 1e-12);
+        assert!(refracted.z() < 0.0);
+
+        // straight through at normal incidence
+        assert_close(
+            Vector::new(0.0, 0.0, -1.0).refract(&n, eta_ratio),
+            Vector::new(0.0, 0.0, -1.0),
+        );
+    }
+
+    #[test]
+    fn refract_beyond_critical_angle_is_not_transmitted() {
+        // sin(theta) * eta_ratio > 1: total internal reflection, callers should reflect instead
+        // as the result is no longer a unit length direction
+        let n = Vector::new(0.0, 0.0, 1.0);
+        let sin_in = 0.9_f64;
+        let incident = Vector::new(sin_in, 0.0, -(1.0 - sin_in * sin_in).sqrt());
+
+        let refracted = incident.refract(&n, 1.5);
+
+        assert!(sin_in * 1.5 > 1.0);
+        assert!((refracted.x() - sin_in * 1.5).abs() < 1e-12);
+        assert!(refracted.sq_norm() > 1.0);
+    }
+
+    #[test]
+    fn lerp_interpolates_endpoints() {
+        let (a, b) = (Vector::new(1.0, 2.0, 3.0), Vector::new(-3.0, 0.0, 5.0));
+
+        assert_close(a.lerp(&b, 0.0), a);
+        assert_close(a.lerp(&b, 1.0), b);
+        assert_close(a.lerp(&b, 0.25), Vector::new(0.0, 1.5, 3.5));
+    }
+
+    #[test]
+    fn operators() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(-2.0, 0.5, 4.0);
+
+        assert_close(a * b, Vector::new(-2.0, 1.0, 12.0));
+        assert_close(-a, Vector::new(-1.0, -2.0, -3.0));
+        assert_close(-&a, Vector::new(-1.0, -2.0, -3.0));
+
+        let mut sum = a;
+        sum += b;
+        assert_close(sum, Vector::new(-1.0, 2.5, 7.0));
+    }
+
+    #[test]
+    fn index_returns_coordinates() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+
+        assert_eq!([a[0], a[1], a[2]], [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range_panics() {
+        let _ = Vector::new(1.0, 2.0, 3.0)[3];
+    }
+}
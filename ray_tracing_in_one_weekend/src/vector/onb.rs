@@ -0,0 +1,51 @@
+use super::Vector;
+
+/// Orthonormal basis `u`, `v`, `w` built around the given vector `w`
+#[derive(Clone, Copy, Debug)]
+pub struct Onb {
+    u: Vector,
+    v: Vector,
+    w: Vector,
+}
+
+impl Onb {
+    /// Creates the right-handed basis (`u x v = w`) with `w` along the given non-zero vector
+    pub fn new(w: &Vector) -> Self
+    where
+        Self: Sized,
+    {
+        // branchless construction by Duff et al. (2017)
+        let w = w.normalize();
+        let sign = 1.0_f64.copysign(w.z());
+        let a = -1.0 / (sign + w.z());
+        let b = w.x() * w.y() * a;
+
+        Self {
+            u: Vector::new(1.0 + sign * w.x().powi(2) * a, sign * b, -sign * w.x()),
+            v: Vector::new(b, sign + w.y().powi(2) * a, -w.y()),
+            w,
+        }
+    }
+
+    pub fn u(&self) -> Vector {
+        self.u
+    }
+
+    pub fn v(&self) -> Vector {
+        self.v
+    }
+
+    pub fn w(&self) -> Vector {
+        self.w
+    }
+
+    /// Converts the vector given in the basis' coordinates into the world coordinates
+    pub fn to_world(&self, local: &Vector) -> Vector {
+        self.u * local.x() + self.v * local.y() + self.w * local.z()
+    }
+
+    /// Converts the vector given in the world coordinates into the basis' coordinates
+    pub fn to_local(&self, world: &Vector) -> Vector {
+        Vector::new(self.u.dot(world), self.v.dot(world), self.w.dot(world))
+    }
+}
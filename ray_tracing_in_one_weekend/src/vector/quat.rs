@@ -0,0 +1,220 @@
+use std::ops::Mul;
+
+use super::{mat3::Mat3, Vector};
+
+/// Quaternion `w + x i + y j + z k`, unit length quaternions represent rotations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Default for Quat {
+    /// Identity rotation
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Quat {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self
+    where
+        Self: Sized,
+    {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Counterclockwise rotation around the non-zero `axis` by the `angle` (in radians)
+    /// when looking from the axis' end
+    pub fn from_axis_angle(axis: &Vector, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+
+        Self::new(cos, axis.x() * sin, axis.y() * sin, axis.z() * sin)
+    }
+
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+
+    /// Vector part `(x, y, z)`
+    pub fn vector(&self) -> Vector {
+        Vector::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(&self, rhs: &Self) -> f64 {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    /// Squared quaternion norm
+    pub fn sq_norm(&self) -> f64 {
+        self.dot(self)
+    }
+
+    pub fn normalize(&self) -> Self {
+        let norm = self.sq_norm().sqrt();
+
+        Self::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm)
+    }
+
+    /// Conjugate quaternion: the inverse rotation for unit length quaternions
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Rotates the vector by the unit length quaternion
+    pub fn rotate(&self, vector: &Vector) -> Vector {
+        // v' = v + 2 w (q x v) + 2 q x (q x v) for the vector part q
+        let q = self.vector();
+        let t = q.cross(vector) * 2.0;
+
+        vector + &(t * self.w) + q.cross(&t)
+    }
+
+    /// Rotation matrix of the unit length quaternion
+    pub fn to_mat3(&self) -> Mat3 {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+
+        Mat3::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+
+    /// Spherical linear interpolation between unit length quaternions:
+    /// the rotation at the fraction `t` (0.0-1.0 range) of the shortest arc from `self` to `rhs`
+    pub fn slerp(&self, rhs: &Self, t: f64) -> Self {
+        // q and -q are the same rotation, the one closer to `self` gives the shortest arc
+        let (rhs, cos) = match self.dot(rhs) {
+            cos if cos < 0.0 => (Self::new(-rhs.w, -rhs.x, -rhs.y, -rhs.z), -cos),
+            cos => (*rhs, cos),
+        };
+
+        // nearly equal rotations are interpolated linearly to avoid dividing by sin(angle) ~ 0
+        let (lhs_weight, rhs_weight) = if cos > 1.0 - 1e-9 {
+            (1.0 - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+
+        Self::new(
+            self.w * lhs_weight + rhs.w * rhs_weight,
+            self.x * lhs_weight + rhs.x * rhs_weight,
+            self.y * lhs_weight + rhs.y * rhs_weight,
+            self.z * lhs_weight + rhs.z * rhs_weight,
+        )
+        .normalize()
+    }
+}
+
+impl Mul<Quat> for Quat {
+    type Output = Quat;
+
+    /// Hamilton product: the rotation `rhs` followed by `self`
+    fn mul(self, rhs: Quat) -> Self::Output {
+        Quat::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn assert_close(lhs: &Quat, rhs: &Quat) {
+        assert!(
+            (lhs.w - rhs.w).abs() < 1e-12 && (lhs.vector() - rhs.vector()).sq_norm() < 1e-24,
+            "{lhs:?} != {rhs:?}"
+        );
+    }
+
+    #[test]
+    fn to_mat3_matches_rotation_matrix() {
+        let axis = Vector::new(1.0, -2.0, 0.5);
+        let angle = 1.2;
+
+        let from_quat = Quat::from_axis_angle(&axis, angle).to_mat3();
+        let expected = Mat3::rotation(&axis, angle);
+
+        for row in 0..3 {
+            for column in 0..3 {
+                assert!((from_quat.get(row, column) - expected.get(row, column)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn rotate_matches_to_mat3() {
+        let rotation = Quat::from_axis_angle(&Vector::new(0.3, 1.0, -0.2), 2.5);
+        let v = Vector::new(1.0, 2.0, 3.0);
+
+        let expected = rotation.to_mat3().transform_vector(&v);
+
+        assert!((rotation.rotate(&v) - expected).sq_norm() < 1e-24);
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let axis = Vector::new(0.0, 0.0, 1.0);
+        let from = Quat::from_axis_angle(&axis, 0.2);
+        let to = Quat::from_axis_angle(&axis, 1.4);
+
+        assert_close(&from.slerp(&to, 0.0), &from);
+        assert_close(&from.slerp(&to, 1.0), &to);
+        assert_close(&from.slerp(&to, 0.5), &Quat::from_axis_angle(&axis, 0.8));
+    }
+
+    #[test]
+    fn slerp_takes_shortest_arc() {
+        let axis = Vector::new(0.0, 1.0, 0.0);
+        let from = Quat::from_axis_angle(&axis, 0.0);
+        // -q is the same rotation as q: slerp should not go the long way around
+        let to = Quat::from_axis_angle(&axis, PI / 2.0);
+        let negated_to = Quat::new(-to.w, -to.x, -to.y, -to.z);
+
+        let midpoint = from.slerp(&negated_to, 0.5);
+
+        assert_close(&midpoint, &Quat::from_axis_angle(&axis, PI / 4.0));
+        assert_close(&from.slerp(&negated_to, 1.0), &to);
+    }
+}
@@ -0,0 +1,257 @@
+//! Random directions and points with known probability densities.
+//!
+//! Directions are unit length, their densities are given per unit solid angle,
+//! the densities of points are given per unit area
+
+use std::f64::consts::PI;
+
+use rand::{Rng, RngCore};
+
+use super::{onb::Onb, Vector};
+
+/// Generates a direction uniformly distributed over the unit sphere
+pub fn sample_uniform_sphere(rng: &mut dyn RngCore) -> Vector {
+    let z = 1.0 - 2.0 * rng.gen::<f64>();
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let phi = 2.0 * PI * rng.gen::<f64>();
+
+    Vector::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+pub fn uniform_sphere_pdf() -> f64 {
+    1.0 / (4.0 * PI)
+}
+
+/// Generates a direction uniformly distributed over the hemisphere around `normal`
+pub fn sample_uniform_hemisphere(normal: &Vector, rng: &mut dyn RngCore) -> Vector {
+    let z = rng.gen::<f64>();
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let phi = 2.0 * PI * rng.gen::<f64>();
+
+    Onb::new(normal).to_world(&Vector::new(r * phi.cos(), r * phi.sin(), z))
+}
+
+/// Returns the density of `sample_uniform_hemisphere` generating `direction` around `normal`
+pub fn uniform_hemisphere_pdf(normal: &Vector, direction: &Vector) -> f64 {
+    if normal.dot(direction) > 0.0 {
+        1.0 / (2.0 * PI)
+    } else {
+        0.0
+    }
+}
+
+/// Generates a direction uniformly distributed over the cone around `axis`:
+/// the directions whose angle with `axis` has the cosine of at least `cos_theta_max`
+pub fn sample_uniform_cone(axis: &Vector, cos_theta_max: f64, rng: &mut dyn RngCore) -> Vector {
+    let z = 1.0 - rng.gen::<f64>() * (1.0 - cos_theta_max);
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let phi = 2.0 * PI * rng.gen::<f64>();
+
+    Onb::new(axis).to_world(&Vector::new(r * phi.cos(), r * phi.sin(), z))
+}
+
+/// Returns the density of `sample_uniform_cone` generating any direction inside the cone
+pub fn uniform_cone_pdf(cos_theta_max: f64) -> f64 {
+    1.0 / (2.0 * PI * (1.0 - cos_theta_max))
+}
+
+/// Generates a direction over the hemisphere around `normal`
+/// distributed by the cosine of the angle with `normal`
+pub fn sample_cosine_hemisphere(normal: &Vector, rng: &mut dyn RngCore) -> Vector {
+    // uniform points of the unit disk projected up onto the hemisphere (Malley's method)
+    let (x, y) = sample_concentric_disk(rng);
+    let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+
+    Onb::new(normal).to_world(&Vector::new(x, y, z))
+}
+
+/// Returns the density of `sample_cosine_hemisphere` generating `direction` (unit length)
+/// around `normal` (unit length)
+pub fn cosine_hemisphere_pdf(normal: &Vector, direction: &Vector) -> f64 {
+    normal.dot(direction).max(0.0) / PI
+}
+
+/// Generates a point uniformly distributed over the unit disk centered at (0, 0, 0)
+/// perpendicular to `normal`
+pub fn sample_uniform_disk(normal: &Vector, rng: &mut dyn RngCore) -> Vector {
+    let (x, y) = sample_concentric_disk(rng);
+
+    Onb::new(normal).to_world(&Vector::new(x, y, 0.0))
+}
+
+pub fn uniform_disk_pdf() -> f64 {
+    1.0 / PI
+}
+
+/// Generates a point (x, y) uniformly distributed over the unit disk
+/// by mapping concentric squares to concentric circles (Shirley & Chiu, 1997)
+fn sample_concentric_disk(rng: &mut dyn RngCore) -> (f64, f64) {
+    let a: f64 = rng.gen_range(-1.0..1.0);
+    let b: f64 = rng.gen_range(-1.0..1.0);
+
+    if a == 0.0 && b == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let (r, theta) = if a.abs() > b.abs() {
+        (a, PI / 4.0 * (b / a))
+    } else {
+        (b, PI / 2.0 - PI / 4.0 * (a / b))
+    };
+
+    (r * theta.cos(), r * theta.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    const SAMPLES: usize = 200_000;
+    const BINS: usize = 20;
+
+    fn basis() -> Onb {
+        Onb::new(&Vector::new(1.0, -2.0, 0.5))
+    }
+
+    fn normal() -> Vector {
+        basis().w()
+    }
+
+    /// Checks the values (expected in 0.0-1.0 range) are uniformly distributed
+    /// by their histogram
+    fn assert_uniform(values: impl Iterator<Item = f64>, name: &str) {
+        let mut histogram = [0usize; BINS];
+        for value in values {
+            assert!(
+                (0.0..=1.0).contains(&value),
+                "{name}: {value} is out of range"
+            );
+            histogram[((value * BINS as f64) as usize).min(BINS - 1)] += 1;
+        }
+
+        let expected = SAMPLES as f64 / BINS as f64;
+        for (bin, count) in histogram.into_iter().enumerate() {
+            assert!(
+                (count as f64 - expected).abs() < 4.0 * expected.sqrt(),
+                "{name}: bin {bin} has {count} samples, expected {expected}"
+            );
+        }
+    }
+
+    /// Generates the samples checking they are unit length
+    /// (and inside the hemisphere around the normal if `is_hemisphere`)
+    fn gen_directions(
+        sample: impl Fn(&mut dyn RngCore) -> Vector,
+        is_hemisphere: bool,
+    ) -> Vec<Vector> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let normal = normal();
+
+        (0..SAMPLES)
+            .map(|_| {
+                let direction = sample(&mut rng);
+                assert!((direction.sq_norm() - 1.0).abs() < 1e-9);
+                assert!(!is_hemisphere || normal.dot(&direction) >= 0.0);
+
+                direction
+            })
+            .collect()
+    }
+
+    /// Angle around the normal scaled into 0.0-1.0 range
+    fn azimuth(direction: &Vector) -> f64 {
+        let local = basis().to_local(direction);
+
+        (local.y().atan2(local.x()) + PI) / (2.0 * PI)
+    }
+
+    fn mean(values: impl Iterator<Item = f64>) -> f64 {
+        values.sum::<f64>() / SAMPLES as f64
+    }
+
+    #[test]
+    fn uniform_sphere() {
+        let normal = normal();
+        let directions = gen_directions(sample_uniform_sphere, false);
+
+        // the projection onto any axis is uniform over -1.0-1.0 (Archimedes' hat-box theorem)
+        assert_uniform(
+            directions.iter().map(|d| (normal.dot(d) + 1.0) / 2.0),
+            "cos",
+        );
+        assert_uniform(directions.iter().map(azimuth), "azimuth");
+        assert!((mean(directions.iter().map(|d| normal.dot(d).powi(2))) - 1.0 / 3.0).abs() < 5e-3);
+    }
+
+    #[test]
+    fn uniform_hemisphere() {
+        let normal = normal();
+        let directions = gen_directions(|rng| sample_uniform_hemisphere(&normal, rng), true);
+
+        assert_uniform(directions.iter().map(|d| normal.dot(d)), "cos");
+        assert_uniform(directions.iter().map(azimuth), "azimuth");
+        assert!((mean(directions.iter().map(|d| normal.dot(d))) - 0.5).abs() < 5e-3);
+        assert!(directions
+            .iter()
+            .all(|d| uniform_hemisphere_pdf(&normal, d) == 1.0 / (2.0 * PI)));
+        assert_eq!(uniform_hemisphere_pdf(&normal, &-normal), 0.0);
+    }
+
+    #[test]
+    fn cosine_hemisphere() {
+        let normal = normal();
+        let directions = gen_directions(|rng| sample_cosine_hemisphere(&normal, rng), true);
+
+        // the density of the cosine is `2 cos`, so its square is uniform
+        assert_uniform(directions.iter().map(|d| normal.dot(d).powi(2)), "cos^2");
+        assert_uniform(directions.iter().map(azimuth), "azimuth");
+        assert!((mean(directions.iter().map(|d| normal.dot(d))) - 2.0 / 3.0).abs() < 5e-3);
+
+        // the mean inverse density is the hemisphere's solid angle,
+        // it grows near the horizon so the estimate converges slowly
+        let measure = mean(
+            directions
+                .iter()
+                .map(|d| 1.0 / cosine_hemisphere_pdf(&normal, d)),
+        );
+        assert!((measure / (2.0 * PI) - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn uniform_cone() {
+        let normal = normal();
+        let cos_theta_max = 0.6;
+        let directions =
+            gen_directions(|rng| sample_uniform_cone(&normal, cos_theta_max, rng), true);
+
+        // the cosine is uniform over cos_theta_max-1.0
+        assert_uniform(
+            directions
+                .iter()
+                .map(|d| (normal.dot(d) - cos_theta_max) / (1.0 - cos_theta_max)),
+            "cos",
+        );
+        assert_uniform(directions.iter().map(azimuth), "azimuth");
+        // the density integrates to 1 over the cone's solid angle
+        assert!(
+            (uniform_cone_pdf(cos_theta_max) * 2.0 * PI * (1.0 - cos_theta_max) - 1.0).abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn uniform_disk() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let normal = normal();
+        let points = (0..SAMPLES)
+            .map(|_| sample_uniform_disk(&normal, &mut rng))
+            .collect::<Vec<_>>();
+
+        assert!(points.iter().all(|p| normal.dot(p).abs() < 1e-9));
+        // the area within the radius r is proportional to r^2
+        assert_uniform(points.iter().map(Vector::sq_norm), "r^2");
+        assert_uniform(points.iter().map(azimuth), "angle");
+    }
+}